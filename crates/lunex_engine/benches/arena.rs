@@ -0,0 +1,44 @@
+//! Compares computation of a 20k node tree on both storages.
+//! Run with `cargo bench -p lunex_engine --bench arena`.
+
+use std::time::{Duration, Instant};
+use glam::{Vec2, Vec4};
+use lunex_engine::prelude::*;
+use lunex_engine::{NodeGeneralTrait, Rectangle2D, Solid, UiArena, UiNodeTreeComputeTrait, Window};
+
+/// Builds a wide tree mixing all layout types.
+fn sample_tree(width: usize) -> UiTree {
+    let mut tree: UiTree = UiTree::new("Root");
+    for i in 0..width {
+        let window = format!("w{}", i);
+        Window::new().pos(Prc(Vec2::splat(i as f32))).size(Prc(Vec2::splat(50.0))).build(&mut tree, window.as_str()).unwrap();
+        tree.borrow_node_mut(window.as_str()).unwrap().data.as_mut().unwrap().stack = FlexBox::new().gap(Abs(Vec2::splat(2.0)));
+        for j in 0..width {
+            let div = format!("{}/d{}", window, j);
+            let layout = if j % 3 == 2 { Div::new().pad(Abs(Vec4::splat(1.0))).br() } else { Div::new().margin(Abs(Vec4::splat(3.0))) };
+            layout.build(&mut tree, div.as_str()).unwrap();
+            tree.borrow_node_mut(div.as_str()).unwrap().data.as_mut().unwrap().content_size = Vec2::new(10.0, 5.0 + j as f32);
+            Solid::new().size(Abs(Vec2::new(2.0, 1.0))).build(&mut tree, format!("{}/s", div).as_str()).unwrap();
+        }
+    }
+    tree
+}
+
+/// Returns the average duration of the closure over the given number of runs.
+fn measure(runs: u32, mut f: impl FnMut()) -> Duration {
+    let start = Instant::now();
+    for _ in 0..runs { f() }
+    start.elapsed() / runs
+}
+
+fn main() {
+    let mut tree = sample_tree(100);
+    let mut arena: UiArena = tree.clone().into();
+    let root = Rectangle2D::new().with_size((800.0, 600.0)).into();
+
+    let tree_time = measure(20, || tree.compute(root));
+    let arena_time = measure(20, || arena.compute(root));
+
+    println!("{} nodes: UiTree {:?}, UiArena {:?} per compute", arena.len(), tree_time, arena_time);
+    assert!(tree.diff(&arena.into(), 0.0).is_empty());
+}
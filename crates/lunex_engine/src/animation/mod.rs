@@ -0,0 +1,14 @@
+mod structs;
+pub use structs::*;
+
+mod timeline;
+pub use timeline::*;
+
+mod traits;
+pub use traits::*;
+
+pub mod prelude {
+    pub use super::{Ease, LayoutTween, Spring, Transition};
+    pub use super::{Keyframe, Playback, Timeline, TimelinePlayer, Track};
+    pub use super::UiNodeTreeTickTrait;
+}
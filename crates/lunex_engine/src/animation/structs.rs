@@ -0,0 +1,251 @@
+use std::f32::consts::PI;
+use crate::import::*;
+use crate::{Extract, LayoutProperty, LayoutValue, Rectangle3D};
+
+
+// #============#
+// #=== EASE ===#
+
+/// Easing curve mapping linear progress `0.0..=1.0` to the animated progress.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Ease {
+    #[default]
+    Linear,
+    InQuad,
+    OutQuad,
+    InOutQuad,
+    InCubic,
+    OutCubic,
+    InOutCubic,
+    InSine,
+    OutSine,
+    InOutSine,
+    /// Overshoots the target slightly before settling.
+    OutBack,
+}
+impl Ease {
+    /// Maps the progress through the curve. The progress is clamped to `0.0..=1.0` first.
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Ease::Linear => t,
+            Ease::InQuad => t * t,
+            Ease::OutQuad => 1.0 - (1.0 - t) * (1.0 - t),
+            Ease::InOutQuad => if t < 0.5 { 2.0 * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(2) / 2.0 },
+            Ease::InCubic => t * t * t,
+            Ease::OutCubic => 1.0 - (1.0 - t).powi(3),
+            Ease::InOutCubic => if t < 0.5 { 4.0 * t * t * t } else { 1.0 - (-2.0 * t + 2.0).powi(3) / 2.0 },
+            Ease::InSine => 1.0 - (t * PI / 2.0).cos(),
+            Ease::OutSine => (t * PI / 2.0).sin(),
+            Ease::InOutSine => -((t * PI).cos() - 1.0) / 2.0,
+            Ease::OutBack => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            },
+        }
+    }
+}
+
+
+// #=============#
+// #=== TWEEN ===#
+
+/// Animates a single layout property of a node towards the target value.
+/// Add it to the node with [`crate::NodeData::tween`] and advance it with [`crate::UiNodeTreeTickTrait::tick`].
+/// ## 📌 Note
+/// * Tween with unsupported property or a value of a different kind finishes immediately without any change
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LayoutTween {
+    /// Animated property.
+    pub property: LayoutProperty,
+    /// Starting value. If `None`, the current value is used when the tween first advances.
+    pub from: Option<LayoutValue>,
+    /// Target value.
+    pub to: LayoutValue,
+    /// Duration in seconds.
+    pub duration: f32,
+    /// Easing curve.
+    pub ease: Ease,
+    elapsed: f32,
+}
+impl LayoutTween {
+    /// Creates new linear tween of the property from its current value to the target value.
+    pub fn new(property: LayoutProperty, to: impl Into<LayoutValue>, duration: f32) -> Self {
+        LayoutTween { property, from: None, to: to.into(), duration, ease: Ease::Linear, elapsed: 0.0 }
+    }
+    /// Replaces the starting value with the new value.
+    pub fn from(mut self, from: impl Into<LayoutValue>) -> Self {
+        self.from = Some(from.into());
+        self
+    }
+    /// Replaces the easing curve with the new value.
+    pub fn ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+    /// Returns the linear progress in range `0.0..=1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 { 1.0 } else { (self.elapsed / self.duration).clamp(0.0, 1.0) }
+    }
+    /// Returns `true` if the tween reached the target value.
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+    /// Advances the tween by the delta in seconds and writes the value to the target.
+    /// Returns `true` if the tween is still running.
+    pub fn advance(&mut self, target: &mut impl Extract<LayoutProperty, LayoutValue>, delta: f32) -> bool {
+        if self.from.is_none() { self.from = target.get_extract(self.property) }
+        let Some(from) = self.from else { self.elapsed = self.duration; return false };
+
+        self.elapsed += delta;
+        let Some(value) = from.lerp(self.to, self.ease.apply(self.progress())) else { self.elapsed = self.duration; return false };
+        target.set_extract(self.property, value);
+        !self.is_finished()
+    }
+}
+
+
+// #==================#
+// #=== TRANSITION ===#
+
+/// Animates the computed rectangle of a node from the old result to the new one instead of snapping.
+/// The rectangle is tracked relative to the parent, so moving parents don't restart the transitions of their subnodes.
+/// ## 📌 Note
+/// * The first computation snaps to the target
+/// * Stacked subnodes of a transitioning [`crate::Div`] are positioned from its target rectangle
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct Transition {
+    /// Duration in seconds.
+    pub duration: f32,
+    /// Easing curve.
+    pub ease: Ease,
+    from: Option<Rectangle3D>,
+    to: Rectangle3D,
+    elapsed: f32,
+}
+impl Transition {
+    /// Creates new linear transition with the duration in seconds.
+    pub fn new(duration: f32) -> Self {
+        Transition { duration, ..Default::default() }
+    }
+    /// Replaces the easing curve with the new value.
+    pub fn ease(mut self, ease: Ease) -> Self {
+        self.ease = ease;
+        self
+    }
+    /// Returns `true` if the rectangle is animating.
+    pub fn is_running(&self) -> bool {
+        self.from.is_some() && self.elapsed < self.duration
+    }
+    /// Returns the current rectangle relative to the parent.
+    pub fn current(&self) -> Rectangle3D {
+        let Some(from) = self.from else { return self.to };
+        if self.duration <= 0.0 { return self.to }
+        from.lerp(self.to, self.ease.apply(self.elapsed / self.duration))
+    }
+    /// Advances the transition by the delta in seconds. Returns `true` if the transition was running.
+    pub fn advance(&mut self, delta: f32) -> bool {
+        let running = self.is_running();
+        if running { self.elapsed = (self.elapsed + delta).min(self.duration) }
+        running
+    }
+    /// Starts animating towards the new target if it changed and returns the rectangle to use.
+    pub(crate) fn retarget(&mut self, target: Rectangle3D, parent: Rectangle3D) -> Rectangle3D {
+        let offset = parent.pos.truncate().extend(0.0);
+        let mut target = target;
+        target.pos -= offset;
+
+        if self.from.is_none() {
+            self.from = Some(target);
+            self.elapsed = self.duration;
+        } else if target != self.to {
+            self.from = Some(self.current());
+            self.elapsed = 0.0;
+        }
+        self.to = target;
+
+        let mut current = self.current();
+        current.pos += offset;
+        current
+    }
+}
+
+
+// #==============#
+// #=== SPRING ===#
+
+/// Spring smoothing the presented rectangle of a node, see [`crate::NodeData::presented`].
+/// The computed rectangle is the target and the presented rectangle follows it, stepped by [`crate::UiNodeTreeTickTrait::tick`].
+/// Like [`Transition`], the rectangle is tracked relative to the parent, so subnodes move together with their presented parent.
+/// ## 📌 Note
+/// * The first computation snaps to the target
+/// * Only position and size are smoothed, the depth and rotation follow the target directly
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spring {
+    /// Force pulling towards the target per unit of distance.
+    pub stiffness: f32,
+    /// Force slowing down the motion per unit of velocity. Lower values overshoot more.
+    pub damping: f32,
+    /// Position and size as `(x, y, width, height)`.
+    current: Option<Vec4>,
+    target: Vec4,
+    velocity: Vec4,
+}
+impl Default for Spring {
+    fn default() -> Self {
+        Spring::new(170.0, 26.0)
+    }
+}
+impl Spring {
+    /// Longest step of the simulation in seconds, longer deltas are split to keep the spring stable.
+    const STEP: f32 = 1.0 / 120.0;
+    /// Distance and velocity under which the spring snaps to the target.
+    const REST: f32 = 0.01;
+
+    /// Creates new spring with the stiffness and damping.
+    pub fn new(stiffness: f32, damping: f32) -> Self {
+        Spring { stiffness, damping, current: None, target: Vec4::ZERO, velocity: Vec4::ZERO }
+    }
+    /// Returns `true` if the presented rectangle reached the target.
+    pub fn is_settled(&self) -> bool {
+        match self.current {
+            Some(current) => current == self.target && self.velocity == Vec4::ZERO,
+            None => true,
+        }
+    }
+    /// Steps the simulation by the delta in seconds. Returns `true` if the spring was moving.
+    pub fn advance(&mut self, delta: f32) -> bool {
+        if self.is_settled() { return false }
+        let Some(mut current) = self.current else { return false };
+
+        let mut remaining = delta.max(0.0);
+        while remaining > 0.0 {
+            let step = remaining.min(Self::STEP);
+            let force = (self.target - current) * self.stiffness - self.velocity * self.damping;
+            self.velocity += force * step;
+            current += self.velocity * step;
+            remaining -= step;
+        }
+
+        if (self.target - current).abs().max_element() < Self::REST && self.velocity.abs().max_element() < Self::REST {
+            current = self.target;
+            self.velocity = Vec4::ZERO;
+        }
+        self.current = Some(current);
+        true
+    }
+    /// Sets the target relative to the parent and returns the presented rectangle on the presented parent.
+    pub(crate) fn retarget(&mut self, target: Rectangle3D, parent: Rectangle3D, presented_parent: Rectangle3D) -> Rectangle3D {
+        let offset = target.pos.truncate() - parent.pos.truncate();
+        self.target = Vec4::new(offset.x, offset.y, target.size.x, target.size.y);
+        let current = *self.current.get_or_insert(self.target);
+
+        let mut presented = target;
+        presented.pos.x = presented_parent.pos.x + current.x;
+        presented.pos.y = presented_parent.pos.y + current.y;
+        presented.size = Vec2::new(current.z, current.w);
+        presented
+    }
+}
@@ -0,0 +1,215 @@
+use crate::import::*;
+use crate::nodes::prelude::*;
+use crate::{Ease, Extract, LayoutProperty, LayoutValue, UiComponent, UiTree};
+
+
+// #=================#
+// #=== KEYFRAMES ===#
+
+/// Value of a [`Track`] at the given time.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Keyframe {
+    /// Time in seconds from the start of the [`Timeline`].
+    pub time: f32,
+    /// Value of the property at this time.
+    pub value: LayoutValue,
+    /// Easing curve of the segment leading into this keyframe.
+    pub ease: Ease,
+}
+
+/// Keyframes of a single property of the node at the path.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Track {
+    /// Path of the animated node.
+    pub path: String,
+    /// Animated property.
+    pub property: LayoutProperty,
+    #[cfg_attr(feature = "serde", serde(deserialize_with = "sorted_keyframes"))]
+    keyframes: Vec<Keyframe>,
+}
+impl Track {
+    /// Creates new empty track of the property of the node at the path.
+    pub fn new(path: impl Borrow<str>, property: LayoutProperty) -> Self {
+        Track { path: path.borrow().to_owned(), property, keyframes: Vec::new() }
+    }
+    /// Adds a linear keyframe. Keyframes are kept sorted by time, a keyframe at the same time is replaced.
+    pub fn key(self, time: f32, value: impl Into<LayoutValue>) -> Self {
+        self.key_eased(time, value, Ease::Linear)
+    }
+    /// Adds a keyframe reached with the easing curve. Keyframes are kept sorted by time, a keyframe at the same time is replaced.
+    pub fn key_eased(mut self, time: f32, value: impl Into<LayoutValue>, ease: Ease) -> Self {
+        let keyframe = Keyframe { time, value: value.into(), ease };
+        match self.keyframes.binary_search_by(|key| key.time.total_cmp(&time)) {
+            Ok(index) => self.keyframes[index] = keyframe,
+            Err(index) => self.keyframes.insert(index, keyframe),
+        }
+        self
+    }
+    /// Returns the keyframes sorted by time.
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+    /// Returns the time of the last keyframe.
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |key| key.time)
+    }
+    /// Returns the value at the time. Times outside of the keyframes hold the first or the last value.
+    /// ## 📌 Note
+    /// * Segments between values of a different kind hold the earlier value
+    pub fn sample(&self, time: f32) -> Option<LayoutValue> {
+        let next = self.keyframes.partition_point(|key| key.time <= time);
+        if next == 0 { return self.keyframes.first().map(|key| key.value) }
+        let prev = self.keyframes[next - 1];
+        let Some(next) = self.keyframes.get(next) else { return Some(prev.value) };
+
+        let t = (time - prev.time) / (next.time - prev.time);
+        Some(prev.value.lerp(next.value, next.ease.apply(t)).unwrap_or(prev.value))
+    }
+}
+
+/// Deserializes the keyframes sorted by time. Of the keyframes at the same time the last one is kept, like with [`Track::key_eased`].
+#[cfg(feature = "serde")]
+fn sorted_keyframes<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<Keyframe>, D::Error> {
+    let mut keyframes = <Vec<Keyframe> as serde::Deserialize>::deserialize(deserializer)?;
+    keyframes.reverse();
+    keyframes.sort_by(|a, b| a.time.total_cmp(&b.time));
+    keyframes.dedup_by(|a, b| a.time == b.time);
+    Ok(keyframes)
+}
+
+
+// #================#
+// #=== TIMELINE ===#
+
+/// How the [`Timeline`] continues after reaching its end.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Playback {
+    /// Stops at the end.
+    #[default]
+    Once,
+    /// Starts again from the beginning.
+    Loop,
+    /// Plays backwards to the beginning and then forwards again.
+    PingPong,
+}
+
+/// Reusable keyframe animation of multiple nodes. Play it on [`crate::UiTree`] with [`crate::MasterData::play`].
+/// With the `asset` feature it is an `Asset`, so it can be stored in `Assets<Timeline>`. With the `serde` feature it can be (de)serialized.
+/// ```text
+/// let intro = Timeline::new()
+///     .track(Track::new("menu", LayoutProperty::WindowPos)
+///         .key(0.0, Prc(Vec2::new(-100.0, 0.0)))
+///         .key_eased(0.5, Prc(Vec2::ZERO), Ease::OutBack))
+///     .playback(Playback::Once);
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "asset", derive(bevy::asset::Asset, bevy::reflect::TypePath))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Timeline {
+    /// Animated tracks.
+    pub tracks: Vec<Track>,
+    /// Behaviour after the end.
+    pub playback: Playback,
+}
+impl Timeline {
+    /// Creates new empty timeline played once.
+    pub fn new() -> Self {
+        Default::default()
+    }
+    /// Adds the track.
+    pub fn track(mut self, track: Track) -> Self {
+        self.tracks.push(track);
+        self
+    }
+    /// Replaces the playback with the new value.
+    pub fn playback(mut self, playback: Playback) -> Self {
+        self.playback = playback;
+        self
+    }
+    /// Returns the time of the last keyframe of all tracks.
+    pub fn duration(&self) -> f32 {
+        self.tracks.iter().map(Track::duration).fold(0.0, f32::max)
+    }
+    /// Maps the elapsed time to the time on the timeline according to the playback.
+    pub fn position(&self, elapsed: f32) -> f32 {
+        let duration = self.duration();
+        if duration <= 0.0 { return 0.0 }
+        match self.playback {
+            Playback::Once => elapsed.clamp(0.0, duration),
+            Playback::Loop => elapsed.rem_euclid(duration),
+            Playback::PingPong => {
+                let cycle = elapsed.rem_euclid(duration * 2.0);
+                if cycle <= duration { cycle } else { duration * 2.0 - cycle }
+            },
+        }
+    }
+}
+
+/// Playing [`Timeline`] with its elapsed time, stored in [`crate::MasterData`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimelinePlayer {
+    /// Played timeline.
+    pub timeline: Timeline,
+    /// Multiplier of the delta time.
+    pub speed: f32,
+    /// If the time advances.
+    pub paused: bool,
+    elapsed: f32,
+    /// Track paths with the ids of their nodes, looked up once.
+    targets: Vec<(String, Option<NodeId>)>,
+}
+impl TimelinePlayer {
+    /// Creates new player starting at the beginning.
+    pub fn new(timeline: Timeline) -> Self {
+        TimelinePlayer { timeline, speed: 1.0, paused: false, elapsed: 0.0, targets: Vec::new() }
+    }
+    /// Replaces the speed with the new value.
+    pub fn speed(mut self, speed: f32) -> Self {
+        self.speed = speed;
+        self
+    }
+    /// Returns the elapsed time in seconds.
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+    /// Moves the player to the elapsed time in seconds.
+    pub fn seek(&mut self, elapsed: f32) {
+        self.elapsed = elapsed;
+    }
+    /// Returns `true` if [`Playback::Once`] timeline reached its end.
+    pub fn is_finished(&self) -> bool {
+        self.timeline.playback == Playback::Once && self.elapsed >= self.timeline.duration()
+    }
+    /// Advances the time by the delta in seconds, unless paused.
+    pub fn advance(&mut self, delta: f32) {
+        if !self.paused { self.elapsed += delta * self.speed }
+    }
+    /// Returns the sampled values of all tracks at the current time.
+    pub fn sample(&self) -> impl Iterator<Item = (&str, LayoutProperty, LayoutValue)> {
+        let time = self.timeline.position(self.elapsed);
+        self.timeline.tracks.iter().filter_map(move |track| Some((track.path.as_str(), track.property, track.sample(time)?)))
+    }
+    /// Writes the sampled values of all tracks into the nodes of the tree.
+    /// Nodes are looked up by the track paths only once and then by their [`NodeId`], so moved nodes keep animating.
+    pub(crate) fn apply<M: Default + UiComponent, N: Default + UiComponent>(&mut self, tree: &mut UiTree<M, N>) {
+        let time = self.timeline.position(self.elapsed);
+        self.targets.truncate(self.timeline.tracks.len());
+        for (index, track) in self.timeline.tracks.iter().enumerate() {
+            // Tracks replaced since the last lookup are looked up again
+            match self.targets.get_mut(index) {
+                Some((path, target)) if *path != track.path => (*path, *target) = (track.path.clone(), None),
+                Some(_) => {},
+                None => self.targets.push((track.path.clone(), None)),
+            }
+            let Some(value) = track.sample(time) else { continue };
+
+            let target = &mut self.targets[index].1;
+            if !target.is_some_and(|id| tree.contains_id(id)) { *target = tree.node_id(track.path.as_str()).ok() }
+            let Some(id) = *target else { continue };
+            if let Ok(Some(data)) = tree.borrow_data_by_id_mut(id) { data.set_extract(track.property, value); }
+        }
+    }
+}
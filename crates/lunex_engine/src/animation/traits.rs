@@ -0,0 +1,245 @@
+use crate::{Rectangle3D, UiComponent, UiCursorMutTrait, UiNode, UiTree};
+
+
+// #===================#
+// #=== TICK TRAITS ===#
+
+/// Trait with [`UiTree`] animation methods.
+pub trait UiNodeTreeTickTrait {
+    /// ## 🚸 Recursive
+    /// Advances all timelines, tweens, transitions and springs by the delta in seconds. Finished timelines and tweens are removed.
+    /// Timelines are applied first, so tweens of the same property win.
+    /// Returns `true` if anything animated, meaning the tree needs to be computed again.
+    fn tick(&mut self, delta: f32) -> bool;
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeTickTrait for UiTree<M, N> {
+    fn tick(&mut self, delta: f32) -> bool {
+        let mut timelines = self.data.as_mut().map(|master| std::mem::take(&mut master.timelines)).unwrap_or_default();
+        let mut animated = false;
+        for player in timelines.values_mut() {
+            if player.paused { continue }
+            animated = true;
+            player.advance(delta);
+            player.apply(self);
+        }
+        timelines.retain(|_, player| !player.is_finished());
+        if let Some(master) = &mut self.data { master.timelines = timelines }
+
+        animated |= self.node.tick(delta);
+        animated
+    }
+}
+impl <N: Default + UiComponent> UiNodeTreeTickTrait for UiNode<N> {
+    fn tick(&mut self, delta: f32) -> bool {
+        let mut animated = false;
+        if let Some(data) = &mut self.data {
+            let mut tweens = std::mem::take(&mut data.tweens);
+            animated |= !tweens.is_empty();
+            tweens.retain_mut(|tween| tween.advance(data, delta));
+            data.tweens = tweens;
+
+            if let Some(transition) = &mut data.transition {
+                animated |= transition.advance(delta);
+            }
+            if let Some(spring) = &mut data.spring {
+                animated |= spring.advance(delta);
+            }
+        }
+        for subnode in self.nodes.values_mut() {
+            animated |= subnode.tick(delta);
+        }
+        animated
+    }
+}
+
+
+/// Trait writing [`crate::NodeData::presented`] after the layout computation.
+/// Implemented for every [`UiCursorMutTrait`], so [`UiNode`] and [`crate::UiArena`] share it.
+pub(crate) trait UiNodePresentTrait<N: Default + UiComponent>: UiCursorMutTrait<N> {
+    /// ## 🚸 Recursive
+    /// Places the presented rectangles of subnodes on the presented rectangle of their parent and steers their springs.
+    fn present(&mut self, parent: Rectangle3D, presented_parent: Rectangle3D) {
+        let Some(data) = self.data() else { return };
+        data.presented = present_node(data, parent, presented_parent);
+
+        let (parent, presented_parent) = (data.rectangle, data.presented);
+        for index in 0..self.len() {
+            if let Some(mut subnode) = self.subnode(index) {
+                subnode.present(parent, presented_parent);
+            }
+        }
+    }
+}
+impl <N: Default + UiComponent, T: UiCursorMutTrait<N> + ?Sized> UiNodePresentTrait<N> for T {}
+
+/// Returns the presented rectangle of the node, offset from its parent like the computed rectangle or moved by its spring.
+fn present_node<N: Default + UiComponent>(data: &mut crate::NodeData<N>, parent: Rectangle3D, presented_parent: Rectangle3D) -> Rectangle3D {
+    if let Some(spring) = &mut data.spring { return spring.retarget(data.rectangle, parent, presented_parent) }
+    let mut presented = data.rectangle;
+    presented.pos.x += presented_parent.pos.x - parent.pos.x;
+    presented.pos.y += presented_parent.pos.y - parent.pos.y;
+    presented
+}
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::import::*;
+    use crate::{Extract, LayoutProperty, LayoutValue, NodeDataTrait, NodeTopDataTrait, Rectangle2D, Window};
+
+    #[test]
+    fn node_size_lerp () {
+        let a: NodeSize<Vec2> = Abs(Vec2::new(10.0, 0.0)).into();
+        let b: NodeSize<Vec2> = Prc(Vec2::new(50.0, 50.0)).into();
+        let mid = a.lerp(b, 0.5);
+        assert_eq!(mid.abs, Some(Vec2::new(5.0, 0.0)));
+        assert_eq!(mid.prc, Some(Vec2::new(25.0, 25.0)));
+        assert_eq!(Ease::InOutQuad.apply(0.5), 0.5);
+        assert_eq!(Ease::OutCubic.apply(2.0), 1.0);
+    }
+
+    #[test]
+    fn tween () {
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().size(Abs(Vec2::new(100.0, 100.0))).build(&mut tree, "panel").unwrap();
+
+        let to: NodeSize<Vec2> = Abs(Vec2::new(200.0, 100.0)).into();
+        let data = tree.borrow_data_mut("panel").unwrap().unwrap();
+        data.tween(LayoutTween::new(LayoutProperty::WindowSize, to, 1.0));
+
+        assert!(tree.tick(0.5));
+        let data = tree.borrow_data("panel").unwrap().unwrap();
+        assert_eq!(data.get_extract(LayoutProperty::WindowSize), Some(LayoutValue::Vec2(Abs(Vec2::new(150.0, 100.0)).into())));
+        assert_eq!(data.tweens.len(), 1);
+
+        assert!(tree.tick(0.5));
+        let data = tree.borrow_data("panel").unwrap().unwrap();
+        assert_eq!(data.get_extract(LayoutProperty::WindowSize), Some(LayoutValue::Vec2(Abs(Vec2::new(200.0, 100.0)).into())));
+        assert!(data.tweens.is_empty());
+        assert!(!tree.tick(0.5));
+    }
+
+    #[test]
+    fn transition () {
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().size(Abs(Vec2::new(100.0, 100.0))).builder().transition(Transition::new(1.0)).build(&mut tree, "panel").unwrap();
+        let root: Rectangle3D = Rectangle2D::new().with_size((800.0, 600.0)).into();
+
+        // First computation snaps
+        tree.compute(root);
+        assert_eq!(tree.borrow_data("panel").unwrap().unwrap().rectangle.size, Vec2::new(100.0, 100.0));
+
+        if let Layout::Window(window) = &mut tree.borrow_data_mut("panel").unwrap().unwrap().layout {
+            window.size = Abs(Vec2::new(200.0, 100.0)).into();
+        }
+        tree.compute(root);
+        assert_eq!(tree.borrow_data("panel").unwrap().unwrap().rectangle.size, Vec2::new(100.0, 100.0));
+
+        assert!(tree.tick(0.25));
+        tree.compute(root);
+        assert_eq!(tree.borrow_data("panel").unwrap().unwrap().rectangle.size, Vec2::new(125.0, 100.0));
+
+        assert!(tree.tick(1.0));
+        tree.compute(root);
+        assert_eq!(tree.borrow_data("panel").unwrap().unwrap().rectangle.size, Vec2::new(200.0, 100.0));
+        assert!(!tree.tick(1.0));
+    }
+
+    #[test]
+    fn timeline () {
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().build(&mut tree, "panel").unwrap();
+
+        let abs = |x: f32| -> NodeSize<Vec2> { Abs(Vec2::new(x, 0.0)).into() };
+        let track = Track::new("panel", LayoutProperty::WindowPos).key(1.0, abs(10.0)).key(0.0, abs(0.0));
+        assert_eq!(track.keyframes()[0].time, 0.0);
+
+        let timeline = Timeline::new().track(track).playback(Playback::PingPong);
+        assert_eq!(timeline.position(1.5), 0.5);
+        assert_eq!(timeline.clone().playback(Playback::Loop).position(1.25), 0.25);
+        assert_eq!(timeline.clone().playback(Playback::Once).position(3.0), 1.0);
+
+        let pos = |tree: &UiTree, path| tree.borrow_data(path).unwrap().unwrap().get_extract(LayoutProperty::WindowPos);
+        let timeline = timeline.playback(Playback::Once);
+        tree.obtain_topdata_mut().unwrap().play("slide", timeline.clone());
+        assert!(tree.tick(0.5));
+        assert_eq!(pos(&tree, "panel"), Some(LayoutValue::Vec2(abs(5.0))));
+
+        // The node is found by id after the first lookup, so it keeps animating after moving
+        tree.move_node("panel", "moved").unwrap();
+        assert!(tree.tick(1.0));
+        assert_eq!(pos(&tree, "moved"), Some(LayoutValue::Vec2(abs(10.0))));
+
+        #[cfg(feature = "asset")]
+        {
+            let mut assets = bevy::asset::Assets::<Timeline>::default();
+            let handle = assets.add(timeline.clone());
+            assert_eq!(assets.get(&handle), Some(&timeline));
+        }
+        assert!(tree.obtain_topdata().unwrap().timelines.is_empty());
+        assert!(!tree.tick(1.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn timeline_serde () {
+        let abs = |x: f32| -> NodeSize<Vec2> { Abs(Vec2::new(x, 0.0)).into() };
+        let timeline = Timeline::new()
+            .track(Track::new("panel", LayoutProperty::WindowPos).key(0.0, abs(0.0)).key_eased(1.0, abs(10.0), Ease::OutBack))
+            .playback(Playback::Loop);
+        let text = ron::to_string(&timeline).unwrap();
+        assert_eq!(ron::from_str::<Timeline>(&text).unwrap(), timeline);
+
+        // Keyframes are sorted on load
+        let text = text.replacen("time:0.0", "time:2.0", 1);
+        let loaded = ron::from_str::<Timeline>(&text).unwrap();
+        assert_eq!(loaded.tracks[0].keyframes().iter().map(|key| key.time).collect::<Vec<_>>(), vec![1.0, 2.0]);
+    }
+
+    #[test]
+    fn spring () {
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().size(Abs(Vec2::new(100.0, 100.0))).build(&mut tree, "panel").unwrap();
+        Window::new().pos(Abs(Vec2::new(10.0, 10.0))).size(Abs(Vec2::new(10.0, 10.0))).build(&mut tree, "panel/icon").unwrap();
+        tree.borrow_data_mut("panel").unwrap().unwrap().spring = Some(Spring::default());
+        let root: Rectangle3D = Rectangle2D::new().with_size((800.0, 600.0)).into();
+
+        // First computation snaps
+        tree.compute(root);
+        assert_eq!(tree.borrow_data("panel").unwrap().unwrap().presented, tree.borrow_data("panel").unwrap().unwrap().rectangle);
+        assert!(!tree.tick(0.1));
+
+        if let Layout::Window(window) = &mut tree.borrow_data_mut("panel").unwrap().unwrap().layout {
+            window.pos = Abs(Vec2::new(100.0, 0.0)).into();
+        }
+        tree.compute(root);
+        let panel = tree.borrow_data("panel").unwrap().unwrap();
+        assert_eq!(panel.rectangle.pos.x, 100.0);
+        assert_eq!(panel.presented.pos.x, 0.0);
+        assert_eq!(tree.borrow_data("panel/icon").unwrap().unwrap().presented.pos.x, 10.0);
+
+        assert!(tree.tick(0.1));
+        tree.compute(root);
+        let x = tree.borrow_data("panel").unwrap().unwrap().presented.pos.x;
+        assert!(x > 0.0 && x < 100.0);
+        assert_eq!(tree.borrow_data("panel/icon").unwrap().unwrap().presented.pos.x, x + 10.0);
+
+        while tree.tick(0.1) { tree.compute(root) }
+        tree.compute(root);
+        assert_eq!(tree.borrow_data("panel").unwrap().unwrap().presented.pos.x, 100.0);
+
+        // Without springs the presented rectangles snap to the computed ones
+        tree.borrow_data_mut("panel").unwrap().unwrap().spring = None;
+        if let Layout::Window(window) = &mut tree.borrow_data_mut("panel").unwrap().unwrap().layout {
+            window.pos = Abs(Vec2::new(50.0, 0.0)).into();
+        }
+        tree.compute(root);
+        for path in ["panel", "panel/icon"] {
+            let data = tree.borrow_data(path).unwrap().unwrap();
+            assert_eq!(data.presented, data.rectangle);
+        }
+    }
+}
@@ -18,6 +18,7 @@ pub mod prelude {
 
     pub use super::{Rectangle2D, Rectangle3D};
     pub use super::YInvert;
+    pub use super::UiComponent;
 }
 
 mod value;
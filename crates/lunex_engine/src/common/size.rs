@@ -90,6 +90,7 @@ pub struct Rem<T>(pub T);
 /// let b: NodeSize<f32> = Abs(40.0) - Prc(5.0); // -> 40px - 5%
 /// let c: NodeSize<f32> = Prc(50.0).into();     // -> 50%
 /// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct NodeSize<T> {
     /// ## Absolute
@@ -107,6 +108,26 @@ pub struct NodeSize<T> {
 // #===============================#
 // #=== GENERIC IMPLEMENTATIONS ===#
 
+// # Impl `lerp`
+impl<T: Add<Output = T> + Sub<Output = T> + Mul<f32, Output = T> + Copy> NodeSize<T> {
+    /// ## Lerp
+    /// Interpolates each unit separately. Unit missing on one side is treated as zero.
+    /// `t = 0.0` returns `self` and `t = 1.0` returns `other`.
+    pub fn lerp(self, other: NodeSize<T>, t: f32) -> Self {
+        let lerp = |a: Option<T>, b: Option<T>| match (a, b) {
+            (Some(a), Some(b)) => Some(a + (b - a) * t),
+            (Some(a), None) => Some(a * (1.0 - t)),
+            (None, Some(b)) => Some(b * t),
+            (None, None) => None,
+        };
+        NodeSize {
+            abs: lerp(self.abs, other.abs),
+            prc: lerp(self.prc, other.prc),
+            rem: lerp(self.rem, other.rem),
+        }
+    }
+}
+
 // # Impl `with_abs` and `set_abs` ...
 impl<T> NodeSize<T> {
     /// ## With
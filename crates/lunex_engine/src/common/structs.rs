@@ -13,6 +13,19 @@ pub enum UiError {
     /// Error that occurs when something went wrong with NodeTree.
     #[error("NodeTree error: {0}")]
     NodeError(NodeError),
+
+    /// Error that occurs when a size, scale or rectangle of the node at the path is invalid or not finite.
+    /// Empty path refers to the root or the tree itself.
+    #[error("Invalid or non-finite size at '{0}'")]
+    InvalidSize(String),
+
+    /// Error that occurs when the node at the path has no node data.
+    #[error("Node '{0}' is missing Ui data")]
+    MissingData(String),
+
+    /// Error that occurs when the layout of the node at the path can't be used where it is placed.
+    #[error("Layout of '{0}' is not supported in this place of the hierarchy")]
+    UnsupportedNesting(String),
 }
 impl From<NodeError> for UiError {
     fn from(value: NodeError) -> Self {
@@ -86,6 +99,18 @@ pub struct Rectangle3D {
     pub yaw : f32,
     pub tilt: f32,
 }
+impl Rectangle3D {
+    /// Interpolates all fields of the rectangle. `t = 0.0` returns `self` and `t = 1.0` returns `other`.
+    pub fn lerp(self, other: Rectangle3D, t: f32) -> Self {
+        Rectangle3D {
+            pos: self.pos.lerp(other.pos, t),
+            size: self.size.lerp(other.size, t),
+            roll: self.roll + (other.roll - self.roll) * t,
+            yaw: self.yaw + (other.yaw - self.yaw) * t,
+            tilt: self.tilt + (other.tilt - self.tilt) * t,
+        }
+    }
+}
 impl Into<Rectangle2D> for Rectangle3D {
     fn into(self) -> Rectangle2D {
         Rectangle2D {
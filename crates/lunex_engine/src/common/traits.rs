@@ -11,6 +11,31 @@ pub trait NiceDisplay {
 }
 
 
+/// ## Ui component
+/// Bound for user data stored inside [`crate::UiTree`] and its nodes.
+/// With the `bevy` feature enabled it requires the type to be a Bevy `Component`, otherwise it is implemented for every type.
+#[cfg(feature = "bevy")]
+pub trait UiComponent: Component {}
+#[cfg(feature = "bevy")]
+impl <T: Component> UiComponent for T {}
+
+/// ## Ui component
+/// Bound for user data stored inside [`crate::UiTree`] and its nodes.
+/// With the `bevy` feature enabled it requires the type to be a Bevy `Component`, otherwise it is implemented for every type.
+#[cfg(all(not(feature = "bevy"), not(feature = "parallel")))]
+pub trait UiComponent {}
+#[cfg(all(not(feature = "bevy"), not(feature = "parallel")))]
+impl <T> UiComponent for T {}
+
+/// ## Ui component
+/// Bound for user data stored inside [`crate::UiTree`] and its nodes.
+/// With the `parallel` feature and without `bevy` it requires the type to be `Send + Sync`, so subtrees can be computed on other threads.
+#[cfg(all(not(feature = "bevy"), feature = "parallel"))]
+pub trait UiComponent: Send + Sync {}
+#[cfg(all(not(feature = "bevy"), feature = "parallel"))]
+impl <T: Send + Sync> UiComponent for T {}
+
+
 /// ## Y invert
 /// Trait for implementing Y value invert for Glam types due to inverted coordinate system between Ui and Bevy.
 pub trait YInvert {
@@ -0,0 +1,409 @@
+use std::borrow::Borrow;
+use crate::import::*;
+use crate::nodes::prelude::*;
+use crate::{FlexBox, Layout, Window, Solid, Div, Transition, UiComponent};
+use super::UiStorageTrait;
+
+
+// #===============#
+// #=== BUILDER ===#
+
+/// Closure building subnodes of the node created by [`UiBuilder`].
+type UiChildren<'a, S, N> = Box<dyn FnOnce(&mut UiScope<'_, S, N>) -> Result<(), NodeError> + 'a>;
+
+/// Fluent builder creating a node in [`super::UiTree`] or [`super::UiArena`] together with its data and subnodes.
+/// Start it with [`crate::BuildAsNode::builder`] on any layout or with [`UiBuilder::new`].
+/// ## 📌 Note
+/// * Missing nodes in the path are created with default [`super::NodeData`]
+/// * If anything fails, all nodes created by the build are removed again
+pub struct UiBuilder<'a, S: UiStorageTrait<N>, N: Default + UiComponent> {
+    layout: Layout,
+    data: Option<N>,
+    stack: Option<FlexBox>,
+    font_size: Option<f32>,
+    content_size: Option<Vec2>,
+    transition: Option<Transition>,
+    children: Vec<UiChildren<'a, S, N>>,
+}
+impl <'a, S: UiStorageTrait<N>, N: Default + UiComponent> UiBuilder<'a, S, N> {
+    /// Creates new builder with the given layout.
+    pub fn new(layout: impl Into<Layout>) -> Self {
+        UiBuilder {
+            layout: layout.into(),
+            data: None,
+            stack: None,
+            font_size: None,
+            content_size: None,
+            transition: None,
+            children: Vec::new(),
+        }
+    }
+    /// Sets the user data of the node.
+    pub fn data(mut self, data: N) -> Self {
+        self.data = Some(data);
+        self
+    }
+    /// Sets how the subnodes are stacked.
+    pub fn stack(mut self, stack: FlexBox) -> Self {
+        self.stack = Some(stack);
+        self
+    }
+    /// Sets the font size override of the node.
+    pub fn font_size(mut self, font_size: f32) -> Self {
+        self.font_size = Some(font_size);
+        self
+    }
+    /// Sets the size of the content to wrap around.
+    pub fn content_size(mut self, size: impl Into<Vec2>) -> Self {
+        self.content_size = Some(size.into());
+        self
+    }
+    /// Sets the transition animating the computed rectangle of the node.
+    pub fn transition(mut self, transition: Transition) -> Self {
+        self.transition = Some(transition);
+        self
+    }
+    /// Adds a closure that builds subnodes. Paths used in the [`UiScope`] are relative to the new node.
+    /// Can be called multiple times, closures run in order.
+    pub fn children(mut self, children: impl FnOnce(&mut UiScope<'_, S, N>) -> Result<(), NodeError> + 'a) -> Self {
+        self.children.push(Box::new(children));
+        self
+    }
+    /// Builds the node at the given path and returns its full path.
+    /// ## ⚠️ Warning
+    /// * Nothing is changed if an error is returned.
+    pub fn build(self, ui: &mut S, path: impl Borrow<str>) -> Result<String, NodeError> {
+        let path = path.borrow();
+        let rempath = path.rsplit_once('/').map(|(rempath, _)| rempath);
+
+        // The first missing node in the path is the root of everything this build creates.
+        // Existing nodes before it only get default data if they had none.
+        let mut missing = None;
+        let mut plain = Vec::new();
+        if let Some(rempath) = rempath {
+            let mut prefix = String::new();
+            for name in rempath.split('/') {
+                prefix = if prefix.is_empty() { name.to_owned() } else { format!("{}/{}", prefix, name) };
+                match ui.borrow_data(prefix.as_str()) {
+                    Ok(Some(_)) => {},
+                    Ok(None) => plain.push(prefix.clone()),
+                    Err(_) => { missing = Some(prefix); break },
+                }
+            }
+        }
+
+        let path = match ui.create_ui_node(path) {
+            Ok(name) => match rempath {
+                Some(rempath) => format!("{}/{}", rempath, name),
+                None => name,
+            },
+            Err(error) => {
+                rollback(ui, missing, plain);
+                return Err(error);
+            },
+        };
+        match self.fill(ui, &path) {
+            Ok(()) => Ok(path),
+            Err(error) => {
+                rollback(ui, Some(missing.unwrap_or(path)), plain);
+                Err(error)
+            },
+        }
+    }
+    /// Sets the data of the created node and builds its subnodes.
+    fn fill(self, ui: &mut S, path: &str) -> Result<(), NodeError> {
+        let Some(container) = ui.borrow_data_mut(path)? else { return Err(NodeError::NoData(path.to_owned())) };
+        container.layout = self.layout;
+        if let Some(data) = self.data { container.data = Some(data) }
+        if let Some(stack) = self.stack { container.stack = stack }
+        if let Some(font_size) = self.font_size { container.font_size = Some(font_size) }
+        if let Some(size) = self.content_size { container.content_size = size }
+        if let Some(transition) = self.transition { container.transition = Some(transition) }
+
+        let mut scope = UiScope { ui, path: path.to_owned(), marker: std::marker::PhantomData };
+        for children in self.children {
+            children(&mut scope)?;
+        }
+        Ok(())
+    }
+}
+/// Removes the node created by a failed build and the default data it added to existing plain nodes.
+/// Errors are ignored, the error of the build is the one that matters.
+fn rollback<S: UiStorageTrait<N>, N: Default + UiComponent>(ui: &mut S, created: Option<String>, plain: Vec<String>) {
+    if let Some(created) = created { let _ = ui.remove_node(created); }
+    for path in plain {
+        let _ = ui.remove_data(path);
+    }
+}
+impl <'a, S: UiStorageTrait<N>, N: Default + UiComponent> From<Window> for UiBuilder<'a, S, N> {
+    fn from(value: Window) -> Self {
+        UiBuilder::new(value)
+    }
+}
+impl <'a, S: UiStorageTrait<N>, N: Default + UiComponent> From<Solid> for UiBuilder<'a, S, N> {
+    fn from(value: Solid) -> Self {
+        UiBuilder::new(value)
+    }
+}
+impl <'a, S: UiStorageTrait<N>, N: Default + UiComponent> From<Div> for UiBuilder<'a, S, N> {
+    fn from(value: Div) -> Self {
+        UiBuilder::new(value)
+    }
+}
+
+
+// #=============#
+// #=== SCOPE ===#
+
+/// Access to [`super::UiTree`] or [`super::UiArena`] relative to a node, passed to [`UiBuilder::children`] closures.
+pub struct UiScope<'t, S: UiStorageTrait<N>, N: Default + UiComponent> {
+    ui: &'t mut S,
+    path: String,
+    marker: std::marker::PhantomData<N>,
+}
+impl <'t, S: UiStorageTrait<N>, N: Default + UiComponent> UiScope<'t, S, N> {
+    /// Returns the full path of the node this scope is relative to.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    /// Borrows the whole tree. Paths used on it are not relative.
+    pub fn ui(&mut self) -> &mut S {
+        self.ui
+    }
+    /// Builds the subnode at the path relative to this scope and returns its full path.
+    pub fn build<'a>(&mut self, path: impl Borrow<str>, builder: impl Into<UiBuilder<'a, S, N>>) -> Result<String, NodeError> {
+        builder.into().build(self.ui, format!("{}/{}", self.path, path.borrow()))
+    }
+}
+
+
+// #=============#
+// #=== MACRO ===#
+
+/// Declares a [`crate::UiTree`] or [`crate::UiArena`] hierarchy in Rust source. Expands to [`UiBuilder`] calls and returns `Result<(), NodeError>`.
+/// ## 📏 Syntax
+/// Every node is `"name": layout` with an optional `=> { ... }` block. The block starts with settings
+/// (`data`, `stack`, `font_size`, `content_size`, `transition`) followed by subnodes in the same syntax.
+/// ```text
+/// ui!(tree, {
+///     "menu": Window::new().size(Abs(10.0) + Prc(50.0)) => {
+///         stack: FlexBox::new().gap(Abs(Vec2::splat(2.0))),
+///         font_size: 20.0,
+///         "item": Div::new().pad(Abs(4.0)),
+///         "icon": Solid::new(),
+///     },
+/// })?;
+/// ```
+/// ## 📌 Note
+/// * Names containing `'/'`, empty names, `"."` and duplicate sibling names fail at compile time
+/// * A failing top-level node is rolled back, top-level nodes built before it are kept
+/// ```
+/// # use lunex_engine::prelude::*;
+/// # use lunex_engine::{Window, Div};
+/// let mut tree: UiTree<NoData> = UiTree::new("Root");
+/// lunex_engine::ui!(&mut tree, {
+///     "menu": Window::new() => {
+///         "item": Div::new(),
+///     },
+/// }).unwrap();
+/// ```
+/// Invalid names don't compile:
+/// ```compile_fail
+/// # use lunex_engine::prelude::*;
+/// # use lunex_engine::{Window, Div};
+/// let mut tree: UiTree<NoData> = UiTree::new("Root");
+/// lunex_engine::ui!(&mut tree, {
+///     "menu/item": Window::new(),
+/// }).unwrap();
+/// ```
+/// Neither do duplicate sibling names:
+/// ```compile_fail
+/// # use lunex_engine::prelude::*;
+/// # use lunex_engine::{Window, Div};
+/// let mut tree: UiTree<NoData> = UiTree::new("Root");
+/// lunex_engine::ui!(&mut tree, {
+///     "menu": Window::new() => {
+///         "item": Div::new(),
+///         "item": Div::new(),
+///     },
+/// }).unwrap();
+/// ```
+#[macro_export]
+macro_rules! ui {
+    ($ui:expr, { $($body:tt)* }) => {
+        (|| -> ::core::result::Result<(), $crate::NodeError> {
+            let ui = &mut *$ui;
+            $crate::ui!(@children (|name, builder: $crate::UiBuilder<'_, _, _>| builder.build(&mut *ui, name)); $($body)*);
+            Ok(())
+        })()
+    };
+
+    // Settings of the node
+    (@settings ($builder:expr); data: $value:expr $(, $($rest:tt)*)?) => { $crate::ui!(@settings ($builder.data($value)); $($($rest)*)?) };
+    (@settings ($builder:expr); stack: $value:expr $(, $($rest:tt)*)?) => { $crate::ui!(@settings ($builder.stack($value)); $($($rest)*)?) };
+    (@settings ($builder:expr); font_size: $value:expr $(, $($rest:tt)*)?) => { $crate::ui!(@settings ($builder.font_size($value)); $($($rest)*)?) };
+    (@settings ($builder:expr); content_size: $value:expr $(, $($rest:tt)*)?) => { $crate::ui!(@settings ($builder.content_size($value)); $($($rest)*)?) };
+    (@settings ($builder:expr); transition: $value:expr $(, $($rest:tt)*)?) => { $crate::ui!(@settings ($builder.transition($value)); $($($rest)*)?) };
+    (@settings ($builder:expr); ) => { $builder };
+    (@settings ($builder:expr); $($children:tt)+) => {
+        $builder.children(|scope| {
+            $crate::ui!(@children (|name, builder: $crate::UiBuilder<'_, _, _>| scope.build(name, builder)); $($children)+);
+            Ok(())
+        })
+    };
+
+    // Subnodes, built one by one with the supplied closure
+    (@children ($build:expr); $($name:literal : $layout:expr $(=> { $($body:tt)* })?),* $(,)?) => {
+        const _: () = assert!($crate::ui_names_are_valid(&[$($name),*]), "Node names in ui! can't be empty, '.' or contain '/'");
+        const _: () = assert!($crate::ui_names_are_unique(&[$($name),*]), "Sibling node names in ui! must be unique");
+        let mut build = $build;
+        $(
+            build($name, $crate::ui!(@settings ($crate::BuildAsNode::builder($layout)); $($($body)*)?))?;
+        )*
+    };
+}
+
+/// Returns `true` if all names can be used as node names. Used by [`ui!`] at compile time.
+#[doc(hidden)]
+pub const fn ui_names_are_valid(names: &[&str]) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        let name = names[i].as_bytes();
+        if name.is_empty() || (name.len() == 1 && name[0] == b'.') { return false }
+        let mut j = 0;
+        while j < name.len() {
+            if name[j] == b'/' { return false }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Returns `true` if no name is repeated. Used by [`ui!`] at compile time.
+#[doc(hidden)]
+pub const fn ui_names_are_unique(names: &[&str]) -> bool {
+    let mut i = 0;
+    while i < names.len() {
+        let mut j = i + 1;
+        while j < names.len() {
+            let (a, b) = (names[i].as_bytes(), names[j].as_bytes());
+            if a.len() == b.len() {
+                let mut k = 0;
+                while k < a.len() && a[k] == b[k] { k += 1 }
+                if k == a.len() { return false }
+            }
+            j += 1;
+        }
+        i += 1;
+    }
+    true
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::import::*;
+    use crate::{NodeCreationTrait, NodeDataTrait, NodeError, Window, Solid};
+
+    #[cfg_attr(feature = "bevy", derive(Component))]
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Tag(u32);
+
+    #[test]
+    fn builder () {
+        let mut tree: UiTree<NoData, Tag> = UiTree::new("Root");
+        let path = Window::new().builder().data(Tag(1)).stack(FlexBox::new().gap(Abs(Vec2::splat(2.0)))).font_size(20.0)
+            .children(|ui| {
+                ui.build("a", Div::new().builder().content_size((10.0, 10.0)).children(|ui| {
+                    ui.build("s", Solid::new())?;
+                    Ok(())
+                }))?;
+                ui.build("b", Div::new())?;
+                Ok(())
+            })
+            .build(&mut tree, "menu/panel").unwrap();
+        assert_eq!(path, "menu/panel");
+
+        let data = tree.borrow_data("menu/panel").unwrap().unwrap();
+        assert_eq!((data.data.as_ref(), data.font_size), (Some(&Tag(1)), Some(20.0)));
+        assert_eq!(tree.borrow_data("menu/panel/a").unwrap().unwrap().content_size, Vec2::splat(10.0));
+        assert!(tree.borrow_data("menu/panel/a/s").unwrap().is_some());
+        assert!(tree.borrow_data("menu/panel/b").unwrap().is_some());
+
+        // Failing child removes everything the build created
+        let before = tree.clone();
+        let result = Window::new().builder().children(|ui| {
+            ui.build("x", Div::new())?;
+            ui.build("x", Div::new())?;
+            Ok(())
+        }).build(&mut tree, "other/deep/window");
+        assert_eq!(result, Err(NodeError::NameInUse("x".into())));
+        assert_eq!((&tree.data, &tree.node), (&before.data, &before.node));
+
+        let result = Window::new().builder().children(|ui| {
+            ui.build("x/y", Div::new())?;
+            ui.build("x/y", Solid::new())?;
+            Ok(())
+        }).build(&mut tree, "menu/panel/c");
+        assert!(result.is_err());
+        assert_eq!((&tree.data, &tree.node), (&before.data, &before.node));
+
+        // Plain nodes in the path lose the data the build gave them
+        tree.create_node("plain").unwrap();
+        tree.create_node("plain/deeper").unwrap();
+        let before = tree.clone();
+        let result = Window::new().builder().data(Tag(2)).children(|ui| {
+            ui.build("x", Div::new())?;
+            ui.build("x", Div::new())?;
+            Ok(())
+        }).build(&mut tree, "plain/deeper/window");
+        assert_eq!(result, Err(NodeError::NameInUse("x".into())));
+        assert_eq!((&tree.data, &tree.node), (&before.data, &before.node));
+        assert!(Solid::new().builder().build(&mut tree, "plain/deeper/.").is_err());
+        assert_eq!((&tree.data, &tree.node), (&before.data, &before.node));
+
+        // The build error is returned even if the rollback fails
+        let result = Window::new().builder().children(|ui: &mut UiScope<UiTree<NoData, Tag>, Tag>| {
+            let path = ui.path().to_owned();
+            ui.ui().remove_node(path)?;
+            Err(NodeError::NoData("custom".into()))
+        }).build(&mut tree, "gone");
+        assert_eq!(result, Err(NodeError::NoData("custom".into())));
+        assert_eq!((&tree.data, &tree.node), (&before.data, &before.node));
+    }
+
+    #[test]
+    fn macro_tree () {
+        let mut tree: UiTree<NoData, Tag> = UiTree::new("Root");
+        crate::ui!(&mut tree, {
+            "menu": Window::new().size(Abs(Vec2::splat(10.0)) + Prc(Vec2::splat(50.0))) => {
+                data: Tag(3),
+                stack: FlexBox::new().gap(Abs(Vec2::splat(2.0))),
+                font_size: 20.0,
+                "item": Div::new().pad(Abs(Vec4::splat(4.0))) => {
+                    content_size: (5.0, 5.0),
+                },
+                "icon": Solid::new() => {
+                    "inner": Div::new(),
+                },
+            },
+            "empty": Window::new(),
+        }).unwrap();
+
+        let menu = tree.borrow_data("menu").unwrap().unwrap();
+        assert_eq!((menu.data.as_ref(), menu.font_size), (Some(&Tag(3)), Some(20.0)));
+        assert_eq!(tree.borrow_data("menu/item").unwrap().unwrap().content_size, Vec2::splat(5.0));
+        assert!(tree.borrow_data("menu/icon/inner").unwrap().is_some());
+        assert!(tree.borrow_data("empty").unwrap().is_some());
+
+        let result = crate::ui!(&mut tree, { "menu": Window::new() });
+        assert_eq!(result, Err(NodeError::NameInUse("menu".into())));
+        assert!(!super::ui_names_are_valid(&["a/b"]) && !super::ui_names_are_valid(&["."]) && super::ui_names_are_valid(&["..", "a b"]));
+        assert!(!super::ui_names_are_unique(&["a", "b", "a"]) && super::ui_names_are_unique(&["a", "ab"]));
+    }
+}
@@ -1,16 +1,26 @@
+mod builder;
+pub use builder::*;
+
 mod structs;
 pub use structs::*;
 
+mod template;
+pub use template::*;
+
 mod traits;
 pub use traits::*;
 
 pub mod prelude {
-    pub use super::{UiNode, UiTree};
+    pub use super::{UiNode, UiTree, UiArena};
     pub use super::{MasterData, NodeData, NoData};
+    pub use super::{UiDiff, UiChange};
+    pub use super::{UiDiagnostic, UiDiagnosticKind, UiSeverity};
 
-    pub use super::{UiNodeCreationTrait, UiNodeDataTrait, UiNodeTreeInitTrait, UiNodeTreeComputeTrait};
-    pub use super::BuildAsNode;
+    pub use super::{UiNodeCreationTrait, UiNodeDataTrait, UiNodeTreeInitTrait, UiNodeTreeComputeTrait, UiNodeTreeDiffTrait, UiNodeTreeValidateTrait, UiStorageTrait};
+    pub use super::{BuildAsNode, UiBuilder, UiScope};
+    pub use super::Extract;
+    pub use super::UiTemplate;
 
     //RE-EXPORT FROM NODES                          // NEEDS ABSTRACTION
-    pub use crate::nodes::prelude::{NodeGeneralTrait, NodeTopDataTrait, NodeDisplayTrait};
+    pub use crate::nodes::prelude::{NodeGeneralTrait, NodeTopDataTrait, NodeDisplayTrait, NodeIterTrait, NodeVisit, NodeHandleTrait, NodeId, NodeHistory};
 }
\ No newline at end of file
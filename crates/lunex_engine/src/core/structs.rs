@@ -1,9 +1,9 @@
-use crate::{import::*, NiceDisplay, Rectangle3D, FlexBox};
-use bevy::ecs::component::Component;
+use crate::{import::*, NiceDisplay, Rectangle3D, FlexBox, LayoutTween, Spring, Timeline, TimelinePlayer, Transition, UiComponent, UiError};
+use std::fmt;
 use colored::Colorize;
 
 use crate::nodes::prelude::*;
-use crate::layout::Layout;
+use crate::layout::{Layout, WorldAnchor};
 
 /// A struct, `"Document"` / `"DOM"` in web-terms, that contains all layout data. If you want any entity to have it's own ui layout,
 /// add this component to it. The subjects of this layout structure are the entity's children.
@@ -45,35 +45,56 @@ pub type UiTree<M = NoData, N = NoData> = NodeTree<MasterData<M>, NodeData<N>>;
 /// A struct representing organized data in [`UiTree`].
 pub type UiNode<N = NoData> = Node<NodeData<N>>;
 
+/// A [`UiTree`] stored in the flat [`NodeArena`] layout. Computes the same layout,
+/// but avoids per-level allocations and gives `O(1)` access to nodes by [`crate::NodeId`].
+pub type UiArena<M = NoData, N = NoData> = NodeArena<MasterData<M>, NodeData<N>>;
+
 
 
 /// Empty type to tell the compiler that there is no data stored in the node.
-#[derive(Component, Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct NoData;
 
 
 
 /// A struct holding all data appended to [`UiTree`]. Responsible for storing settings, scaling, theme, etc.
 /// Every [`UiTree`] needs to have this to work properly.
-#[derive(Component, Debug, Clone, PartialEq)]
-pub struct MasterData<M: Default + Component> {
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct MasterData<M: Default + UiComponent> {
     /// Mandatory data the user can uppend which all nodes have shared access to.
     pub data: M,
     /// Scale of the [`crate::Abs`] unit.
     pub abs_scale: f32,
     /// Default font size for all subnodes to use (Rem unit scaling).
     pub font_size: f32,
+    /// Playing timelines by name, advanced by [`crate::UiNodeTreeTickTrait::tick`].
+    pub timelines: HashMap<String, TimelinePlayer>,
 }
-impl <M: Default + Component> Default for MasterData<M> {
+impl <M: Default + UiComponent> Default for MasterData<M> {
     fn default() -> Self {
         MasterData {
             data: Default::default(),
             abs_scale: 1.0,
             font_size: 16.0,
+            timelines: Default::default(),
         }
     }
 }
-impl <M: Default + Component> NiceDisplay for MasterData<M> {
+impl <M: Default + UiComponent> MasterData<M> {
+    /// Starts playing the timeline from the beginning. Timeline playing under the same name is replaced.
+    pub fn play(&mut self, name: impl Borrow<str>, timeline: Timeline) -> &mut TimelinePlayer {
+        let name = name.borrow().to_owned();
+        self.timelines.insert(name.clone(), TimelinePlayer::new(timeline));
+        &mut self.timelines[&name]
+    }
+    /// Stops the timeline playing under the name. Animated values are left as they are.
+    pub fn stop(&mut self, name: impl Borrow<str>) -> Option<TimelinePlayer> {
+        self.timelines.shift_remove(name.borrow())
+    }
+}
+impl <M: Default + UiComponent> NiceDisplay for MasterData<M> {
     fn to_nicestr(&self) -> String {
         format!("{}", self.abs_scale)
     }
@@ -82,28 +103,213 @@ impl <M: Default + Component> NiceDisplay for MasterData<M> {
 
 /// A struct holding all data appended to [`UiNode`]. Responsible for storing layout, custom data, cache, etc.
 /// Every [`UiNode`] needs to have this to work properly.
-#[derive(Component, Debug, Default, Clone, PartialEq)]
-pub struct NodeData<N: Default + Component> {
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NodeData<N: Default + UiComponent> {
     /// Optional data the user can append.
     pub data: Option<N>,
     /// Calculated rectangle from layout.
     pub rectangle: Rectangle3D,
     /// Layout of this node.
     pub layout: Layout,
+    /// Optional world-space point the position of [`crate::Window`] layout follows, see [`WorldAnchor`].
+    pub anchor: Option<WorldAnchor>,
     /// Layout of subnodes and how to stack them.
     pub stack: FlexBox,
     /// Optional font size to overwrite the inherited master font size.
     pub font_size: Option<f32>,
     /// Size of the content to wrap around. Affects this node's size only if the layout is parametric (Div).
     pub content_size: Vec2,
+    /// Running layout tweens, advanced by [`crate::UiNodeTreeTickTrait::tick`].
+    pub tweens: Vec<LayoutTween>,
+    /// Optional transition animating the computed rectangle instead of snapping it.
+    pub transition: Option<Transition>,
+    /// Rectangle shown to the user. Same as [`NodeData::rectangle`] offset by the presented ancestors, unless [`NodeData::spring`] is set.
+    /// Use it for drawing and for hit-testing against what is visible, use [`NodeData::rectangle`] for hit-testing against the layout target.
+    pub presented: Rectangle3D,
+    /// Optional spring smoothing the presented rectangle towards the computed rectangle.
+    pub spring: Option<Spring>,
 }
-impl <N:Default + Component> NodeData<N> {
+impl <N:Default + UiComponent> NodeData<N> {
     pub fn new() -> NodeData<N> {
         NodeData::default()
     }
+    /// Starts the tween. Running tween of the same property is replaced.
+    pub fn tween(&mut self, tween: LayoutTween) {
+        self.tweens.retain(|running| running.property != tween.property);
+        self.tweens.push(tween);
+    }
+}
+impl <N: Default + UiComponent + Clone> NodeHistory<NodeData<N>> {
+    /// ## 🚸 Recursive
+    /// Replaces the layout of the node and records the change. Same as [`NodeHistory::modify_data`].
+    pub fn set_layout<S: NodeGeneralTrait<NodeData<N>> + NodeDataTrait<NodeData<N>>>(&mut self, target: &mut S, path: impl Borrow<str>, layout: impl Into<Layout>) -> Result<(), NodeError> {
+        let layout = layout.into();
+        self.modify_data(target, path, |data| data.layout = layout)
+    }
 }
-impl <N: Default + Component> NiceDisplay for NodeData<N> {
+impl <N: Default + UiComponent> NiceDisplay for NodeData<N> {
     fn to_nicestr(&self) -> String {
         format!("{} {} {}", self.layout.to_nicestr(), "|||".black(), self.rectangle.to_nicestr())
     }
-}
\ No newline at end of file
+}
+
+
+/// A single difference between two [`UiTree`]s. Paths are relative to the tree root.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UiChange {
+    /// Node exists only in the new tree. Its subnodes are not reported separately.
+    Added(String),
+    /// Node exists only in the old tree. Its subnodes are not reported separately.
+    Removed(String),
+    /// Node was moved to a different path. Changes inside it are reported with the new path.
+    Moved { from: String, to: String },
+    /// Layout of the node changed.
+    Layout { path: String, old: Box<Layout>, new: Box<Layout> },
+    /// Stacking of the subnodes changed.
+    Stack { path: String, old: FlexBox, new: FlexBox },
+    /// Font size override changed.
+    FontSize { path: String, old: Option<f32>, new: Option<f32> },
+    /// User data of the node changed.
+    Data(String),
+    /// Computed rectangle changed by more than the epsilon.
+    Rectangle { path: String, old: Rectangle3D, new: Rectangle3D },
+}
+impl UiChange {
+    /// Returns the path of the affected node. For moved nodes it is the new path.
+    pub fn path(&self) -> &str {
+        match self {
+            UiChange::Added(path) | UiChange::Removed(path) | UiChange::Data(path) => path,
+            UiChange::Moved { to, .. } => to,
+            UiChange::Layout { path, .. } | UiChange::Stack { path, .. } | UiChange::FontSize { path, .. } | UiChange::Rectangle { path, .. } => path,
+        }
+    }
+}
+impl fmt::Display for UiChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UiChange::Added(path) => write!(f, "+ '{}'", path),
+            UiChange::Removed(path) => write!(f, "- '{}'", path),
+            UiChange::Moved { from, to } => write!(f, "> '{}' -> '{}'", from, to),
+            UiChange::Layout { path, old, new } => write!(f, "~ '{}' layout: {:?} -> {:?}", path, old, new),
+            UiChange::Stack { path, old, new } => write!(f, "~ '{}' stack: {:?} -> {:?}", path, old, new),
+            UiChange::FontSize { path, old, new } => write!(f, "~ '{}' font size: {:?} -> {:?}", path, old, new),
+            UiChange::Data(path) => write!(f, "~ '{}' data", path),
+            UiChange::Rectangle { path, old, new } => write!(f, "~ '{}' rectangle: [pos: {} size: {}] -> [pos: {} size: {}]", path, old.pos, old.size, new.pos, new.size),
+        }
+    }
+}
+
+/// All differences between two [`UiTree`]s, created by [`crate::UiNodeTreeDiffTrait::diff`].
+/// Displays as one change per line, so it can be used directly in assertion messages.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct UiDiff {
+    /// Changes ordered as moves, then changes and removals in old tree order, then additions.
+    pub changes: Vec<UiChange>,
+}
+impl UiDiff {
+    /// Returns `true` if the trees are equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+    /// Returns the number of changes.
+    pub fn len(&self) -> usize {
+        self.changes.len()
+    }
+    /// Returns iterator over all changes.
+    pub fn iter(&self) -> std::slice::Iter<'_, UiChange> {
+        self.changes.iter()
+    }
+    /// Returns iterator over changes affecting the node at the path.
+    pub fn changes_at<'a>(&'a self, path: &'a str) -> impl Iterator<Item = &'a UiChange> {
+        self.changes.iter().filter(move |change| change.path() == path)
+    }
+}
+impl fmt::Display for UiDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+
+
+/// Severity of a [`UiDiagnostic`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum UiSeverity {
+    /// The layout computes, but probably not as intended.
+    Warning,
+    /// The layout can't be computed correctly.
+    Error,
+}
+
+/// Kind of problem found by [`crate::UiNodeTreeValidateTrait::validate`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UiDiagnosticKind {
+    /// Node has no [`NodeData`], so it and all of its subnodes are skipped during computation.
+    MissingData,
+    /// [`crate::Div`] is not inside a laid out node, so it is never computed.
+    ParametricRoot,
+    /// [`crate::Solid`] has zero size on an axis, which computes to `NaN` or infinity.
+    ZeroSolidSize,
+    /// Font size override is not a finite positive number.
+    InvalidFontSize(f32),
+    /// Scale or font size in [`MasterData`] is not a finite positive number.
+    InvalidMasterData,
+    /// Last computed rectangle contains `NaN` or infinity.
+    NonFiniteRectangle,
+}
+impl UiDiagnosticKind {
+    /// Returns the severity of this kind of problem.
+    pub fn severity(&self) -> UiSeverity {
+        match self {
+            UiDiagnosticKind::MissingData => UiSeverity::Warning,
+            _ => UiSeverity::Error,
+        }
+    }
+}
+
+/// A single problem found in [`UiTree`] with the path of the affected node. Root is the empty path.
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiDiagnostic {
+    pub path: String,
+    pub kind: UiDiagnosticKind,
+}
+impl UiDiagnostic {
+    /// Returns the severity of the problem.
+    pub fn severity(&self) -> UiSeverity {
+        self.kind.severity()
+    }
+    /// Returns `true` if the problem is an error.
+    pub fn is_error(&self) -> bool {
+        self.severity() == UiSeverity::Error
+    }
+}
+impl fmt::Display for UiDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity() {
+            UiSeverity::Warning => "warning",
+            UiSeverity::Error => "error",
+        };
+        let message = match self.kind {
+            UiDiagnosticKind::MissingData => "node has no data, it and its subnodes are not computed".to_owned(),
+            UiDiagnosticKind::ParametricRoot => "div is not inside a laid out node and is never computed".to_owned(),
+            UiDiagnosticKind::ZeroSolidSize => "solid has zero size on an axis".to_owned(),
+            UiDiagnosticKind::InvalidFontSize(size) => format!("font size '{}' is not a positive number", size),
+            UiDiagnosticKind::InvalidMasterData => "master data scale or font size is not a positive number".to_owned(),
+            UiDiagnosticKind::NonFiniteRectangle => "computed rectangle is not finite".to_owned(),
+        };
+        write!(f, "{}: '{}' {}", severity, self.path, message)
+    }
+}
+impl From<UiDiagnostic> for UiError {
+    fn from(value: UiDiagnostic) -> Self {
+        match value.kind {
+            UiDiagnosticKind::MissingData => UiError::MissingData(value.path),
+            UiDiagnosticKind::ParametricRoot => UiError::UnsupportedNesting(value.path),
+            UiDiagnosticKind::ZeroSolidSize | UiDiagnosticKind::InvalidFontSize(_) | UiDiagnosticKind::InvalidMasterData | UiDiagnosticKind::NonFiniteRectangle => UiError::InvalidSize(value.path),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::borrow::Borrow;
+use crate::nodes::prelude::*;
+use crate::UiComponent;
+use super::{UiNode, UiTree, UiStorageTrait};
+
+
+// #================#
+// #=== TEMPLATE ===#
+
+/// Closure customizing a fresh copy of [`UiTemplate`] with instance parameters.
+type UiTemplateParams<N, P> = Box<dyn Fn(&mut UiNode<N>, &P) -> Result<(), NodeError>>;
+
+/// A reusable subtree (layouts, stacking and default user data) that can be instantiated under any path.
+/// Each instance is a copy customized by the parameter closure, so labels, sizes or names of subnodes can differ.
+/// ## 📌 Note
+/// * The template remembers its instances by [`NodeId`], so they can be rebuilt with [`UiTemplate::refresh`]
+///   after the template changes, even if they were moved in the meantime
+/// * Instances removed from the tree are forgotten on the next refresh
+pub struct UiTemplate<N: Default + UiComponent + Clone, P = ()> {
+    node: UiNode<N>,
+    params: Option<UiTemplateParams<N, P>>,
+    instances: Vec<(NodeId, P)>,
+}
+impl <N: Default + UiComponent + Clone, P> UiTemplate<N, P> {
+    /// Creates new template from the node. Works with [`UiTree`] too, its root node becomes the template.
+    pub fn new(node: impl Into<UiNode<N>>) -> Self {
+        let mut node = node.into();
+        node.clear_ids();
+        UiTemplate { node, params: None, instances: Vec::new() }
+    }
+    /// Creates new template from a copy of the existing subtree at the path.
+    pub fn capture<M: Default + UiComponent>(ui: &UiTree<M, N>, path: impl Borrow<str>) -> Result<Self, NodeError> {
+        Ok(UiTemplate::new(ui.borrow_node(path)?.clone()))
+    }
+    /// Sets the closure that applies instance parameters to a fresh copy of the template.
+    pub fn with_params(mut self, params: impl Fn(&mut UiNode<N>, &P) -> Result<(), NodeError> + 'static) -> Self {
+        self.params = Some(Box::new(params));
+        self
+    }
+    /// Borrows the template subtree.
+    pub fn node(&self) -> &UiNode<N> {
+        &self.node
+    }
+    /// Borrows the template subtree as mut. Call [`UiTemplate::refresh`] afterwards to update the instances.
+    pub fn node_mut(&mut self) -> &mut UiNode<N> {
+        &mut self.node
+    }
+    /// Returns ids of all instances known to this template.
+    pub fn instances(&self) -> impl Iterator<Item = NodeId> + '_ {
+        self.instances.iter().map(|(id, _)| *id)
+    }
+    /// Inserts new instance at the path (including the name) and returns its id.
+    /// ## ⚠️ Warning
+    /// * Nothing is changed if an error is returned.
+    pub fn instantiate<S: UiStorageTrait<N>>(&mut self, ui: &mut S, path: impl Borrow<str>, params: P) -> Result<NodeId, NodeError> {
+        let node = self.create(&params)?;
+        let id = ui.insert_node(path, node)?;
+        self.instances.push((id, params));
+        Ok(id)
+    }
+    /// Rebuilds all instances from the current template with their original parameters.
+    /// Instances keep their name, position and id, but their data and subnodes are replaced.
+    /// ## ⚠️ Warning
+    /// * Changes made to the instances directly are lost.
+    /// * Nothing is changed if the parameters can't be applied to one of the instances.
+    pub fn refresh<S: UiStorageTrait<N>>(&mut self, ui: &mut S) -> Result<(), NodeError> {
+        self.instances.retain(|(id, _)| ui.contains_id(*id));
+
+        // Fresh copies are created for all instances before any of them is touched
+        let mut fresh_copies = Vec::with_capacity(self.instances.len());
+        for (id, params) in &self.instances {
+            fresh_copies.push((*id, ui.path_by_id(*id)?, self.create(params)?));
+        }
+
+        for (id, path, fresh) in fresh_copies {
+            // Removing the old subnodes one by one releases their ids
+            let node = ui.borrow_node_by_id(id)?;
+            let names: Vec<String> = (0..node.subnode_len()).filter_map(|index| node.subnode_name(index).cloned()).collect();
+            for name in names {
+                ui.remove_node(format!("{}/{}", path, name))?;
+            }
+            for (name, subnode) in fresh.nodes {
+                ui.insert_node(format!("{}/{}", path, name), subnode)?;
+            }
+            match fresh.data {
+                Some(data) => ui.insert_data(path, data)?,
+                None => ui.remove_data(path)?,
+            };
+        }
+        Ok(())
+    }
+    /// Creates a fresh copy of the template with the parameters applied.
+    fn create(&self, params: &P) -> Result<UiNode<N>, NodeError> {
+        let mut node = self.node.clone();
+        if let Some(apply) = &self.params { apply(&mut node, params)? }
+        Ok(node)
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::UiTemplate;
+    use crate::prelude::*;
+    use crate::import::*;
+    use crate::{NodeDataTrait, NodeError, UiNodeDataTrait, Window, Solid};
+
+    #[cfg_attr(feature = "bevy", derive(Component))]
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Label(String);
+
+    #[test]
+    fn template () {
+        let mut button: UiTree<NoData, Label> = UiTree::new("Button");
+        crate::ui!(&mut button, {
+            "text": Div::new() => { content_size: (10.0, 2.0) },
+            "icon": Solid::new(),
+        }).unwrap();
+
+        let mut template = UiTemplate::new(button).with_params(|node, (label, width): &(&str, f32)| {
+            if *width < 75.0 { node.borrow_node("icon")?; }
+            node.insert_ui_data("text", Label(label.to_string()))?;
+            node.obtain_data_mut().unwrap().layout = Window::new().width(Abs(*width)).into();
+            Ok(())
+        });
+
+        let mut tree: UiTree<NoData, Label> = UiTree::new("Root");
+        Window::new().build(&mut tree, "menu").unwrap();
+        let play = template.instantiate(&mut tree, "menu/play", ("Play", 100.0)).unwrap();
+        let quit = template.instantiate(&mut tree, "menu/quit", ("Quit", 50.0)).unwrap();
+        assert_eq!(tree.borrow_ui_data("menu/quit/text"), Ok(Some(&Label("Quit".into()))));
+        assert_eq!(tree.borrow_node("menu/play/icon").unwrap().get_path(), "menu/play/icon");
+        assert!(template.instantiate(&mut tree, "menu/play", ("Again", 1.0)).is_err());
+
+        // Instances are not touched if one of them can't be refreshed
+        template.node_mut().remove_node("icon").unwrap();
+        template.node_mut().borrow_data_mut("text").unwrap().unwrap().content_size = Vec2::new(20.0, 4.0);
+        let before = tree.clone();
+        assert_eq!(template.refresh(&mut tree), Err(NodeError::NoNode("icon".into())));
+        assert_eq!(tree, before);
+
+        // Refresh moved and removed instances
+        let icon = tree.node_id("menu/play/icon").unwrap();
+        let text = tree.node_id("menu/play/text").unwrap();
+        tree.move_node("menu/play", "start").unwrap();
+        tree.remove_node("menu/quit").unwrap();
+        template.refresh(&mut tree).unwrap();
+
+        assert_eq!(template.instances().collect::<Vec<_>>(), vec![play]);
+        assert!(!tree.contains_id(quit));
+        assert!(!tree.contains_id(icon));
+        assert!(!tree.contains_id(text));
+        assert_eq!(tree.borrow_node("start/icon").err(), Some(NodeError::NoNode("icon".into())));
+        assert_eq!(tree.borrow_data("start/text").unwrap().unwrap().content_size, Vec2::new(20.0, 4.0));
+        assert_eq!(tree.borrow_ui_data("start/text"), Ok(Some(&Label("Play".into()))));
+        assert_eq!(tree.borrow_node("start/text").unwrap().get_path(), "start/text");
+        assert_eq!(tree.path_by_id(play).unwrap(), "start");
+    }
+}
@@ -1,7 +1,5 @@
 use std::borrow::Borrow;
 
-use bevy::ecs::component::Component;
-use bevy::math::Vec3Swizzles;
 
 use crate::nodes::prelude::*;
 use crate::layout;
@@ -11,8 +9,13 @@ use crate::NodeSizeEvaluate;
 use crate::Rectangle3D;
 use crate::import::*;
 use crate::FlexDirection;
+use crate::{LayoutProperty, LayoutValue};
+use crate::UiNodePresentTrait;
+use crate::{Transition, Spring};
+use crate::UiError;
+use crate::UiComponent;
 
-use super::{UiNode, UiTree, NodeData};
+use super::{UiNode, UiTree, UiArena, UiBuilder, NodeData, UiDiff, UiChange, UiDiagnostic, UiDiagnosticKind};
 
 
 // #==========================#
@@ -20,77 +23,96 @@ use super::{UiNode, UiTree, NodeData};
 
 /// Trait that abstracts over [`NodeCreationTrait`] to provide tailored
 /// implementations for the primitive in layouting context.
-pub trait UiNodeCreationTrait<N:Default + Component> {
+pub trait UiNodeCreationTrait<N:Default + UiComponent>: NodeGeneralTrait<NodeData<N>> {
     /// Makes new subnode in this node and returns the new subnodes' name.
     /// ## 📌 Note
     /// * Use [`UiNodeCreationTrait::create_ui_node`] for hierarchy creation `(supports path recursion)`
     fn make_ui_node(&mut self, name: impl Borrow<str>) -> Result<String, NodeError>;
     /// ## 🚸 Recursive
     /// Creates new subnode in this node or any other subnode and returns the new subnodes' name.
+    /// Missing nodes in the path are created too. All created nodes get default [`NodeData`].
     /// ## 📌 Note
     /// * Use [`UiNodeCreationTrait::make_ui_node`] for direct creation on this node `(no recursion)`
     fn create_ui_node(&mut self, path: impl Borrow<str>) -> Result<String, NodeError>;
     /// Borrows subnode from this node. If the node doesn't exist, it creates one.
+    /// Existing node without [`NodeData`] gets the default one.
     /// ## 📌 Note
     /// * Use [`UiNodeCreationTrait::borrow_or_create_ui_node`] for hierarchy retrieval `(supports path recursion)`
-    fn obtain_or_create_ui_node(&mut self, name: impl Borrow<str>) -> Result<&UiNode<N>, NodeError>;
+    fn obtain_or_create_ui_node(&mut self, name: impl Borrow<str>) -> Result<&Self::Subnode, NodeError>;
     /// Borrows subnode from this node as mut. If the node doesn't exist, it creates one.
+    /// Existing node without [`NodeData`] gets the default one.
     /// ## 📌 Note
     /// * Use [`UiNodeCreationTrait::borrow_or_create_ui_node_mut`] for hierarchy retrieval `(supports path recursion)`
-    fn obtain_or_create_ui_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut UiNode<N>, NodeError>;
+    fn obtain_or_create_ui_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut Self::Subnode, NodeError>;
     /// ## 🚸 Recursive
     /// Borrows subnode from this node or any other subnode. If a node in path doesn't exist, it creates one.
+    /// All nodes in the path are guaranteed to have [`NodeData`].
     /// ## 📌 Note
     /// * Use [`UiNodeCreationTrait::obtain_or_create_ui_node`] for direct retrieval on this node `(no recursion)`
-    fn borrow_or_create_ui_node(&mut self, path: impl Borrow<str>) -> Result<&UiNode<N>, NodeError>;
+    fn borrow_or_create_ui_node(&mut self, path: impl Borrow<str>) -> Result<&Self::Subnode, NodeError>;
     /// ## 🚸 Recursive
     /// Borrows subnode from this node or any other subnode as mut. If a node in path doesn't exist, it creates one.
+    /// All nodes in the path are guaranteed to have [`NodeData`].
     /// ## 📌 Note
     /// * Use [`UiNodeCreationTrait::obtain_or_create_ui_node_mut`] for direct retrieval on this node `(no recursion)`
-    fn borrow_or_create_ui_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut UiNode<N>, NodeError>;  
+    fn borrow_or_create_ui_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut Self::Subnode, NodeError>;  
 }
-impl <M: Default + Component, N: Default + Component> UiNodeCreationTrait<N> for UiTree<M, N> {
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeCreationTrait<N> for UiTree<M, N> {
     fn make_ui_node(&mut self, name: impl Borrow<str>) -> Result<String, NodeError>{
-        self.node.make_ui_node(name)
+        let name = self.node.make_ui_node(name)?;
+        self.issue_path_ids(&name)?;
+        Ok(name)
     }
     fn create_ui_node(&mut self, path: impl Borrow<str>) -> Result<String, NodeError>{
-        self.node.create_ui_node(path)
+        let name = self.node.create_ui_node(path.borrow())?;
+        match path.borrow().rsplit_once('/') {
+            Some((rempath, _)) => self.issue_path_ids(&format!("{}/{}", rempath, name))?,
+            None => self.issue_path_ids(&name)?,
+        }
+        Ok(name)
     }
     fn obtain_or_create_ui_node(&mut self, name: impl Borrow<str>) -> Result<&UiNode<N>, NodeError> {
-        self.node.obtain_or_create_ui_node(name)
+        self.obtain_or_create_ui_node_mut(name).map(|node| &*node)
     }
     fn obtain_or_create_ui_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut UiNode<N>, NodeError> {
-        self.node.obtain_or_create_ui_node_mut(name)
+        let path = self.node.obtain_or_create_ui_node_mut(name)?.get_path().to_owned();
+        if path.is_empty() { return Ok(&mut self.node) }
+        self.issue_path_ids(&path)?;
+        self.node.borrow_node_mut(path)
     }
     fn borrow_or_create_ui_node(&mut self, path: impl Borrow<str>) -> Result<&UiNode<N>, NodeError> {
-        self.node.borrow_or_create_ui_node(path)
+        self.borrow_or_create_ui_node_mut(path).map(|node| &*node)
     }
     fn borrow_or_create_ui_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut UiNode<N>, NodeError> {
-        self.node.borrow_or_create_ui_node_mut(path)
+        let path = self.node.borrow_or_create_ui_node_mut(path)?.get_path().to_owned();
+        if path.is_empty() { return Ok(&mut self.node) }
+        self.issue_path_ids(&path)?;
+        self.node.borrow_node_mut(path)
     }
 }
-impl <N: Default + Component> UiNodeCreationTrait<N> for UiNode<N> {
+impl <N: Default + UiComponent> UiNodeCreationTrait<N> for UiNode<N> {
     fn make_ui_node(&mut self, name: impl Borrow<str>) -> Result<String, NodeError> {
         let n = self.make_node(name)?;
         self.insert_data(n.clone(), NodeData::default())?;
         Ok(n)
     }
     fn create_ui_node(&mut self, path: impl Borrow<str>) -> Result<String, NodeError> {
-        let mut node: UiNode<N> = Node::new();
-        node.add_data(NodeData::default());
-        self.insert_node(path, Node::new())
+        match path.borrow().rsplit_once('/') {
+            None => self.make_ui_node(path),
+            Some((rempath, name)) => self.borrow_or_create_ui_node_mut(rempath)?.make_ui_node(name),
+        }
     }
     fn obtain_or_create_ui_node(&mut self, name: impl Borrow<str>) -> Result<&UiNode<N>, NodeError> {
-        if let Ok(n) = self.make_ui_node(name.borrow()) {
-            return self.obtain_node(n)
-        }
-        self.obtain_node(name)
+        self.obtain_or_create_ui_node_mut(name).map(|node| &*node)
     }
     fn obtain_or_create_ui_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut UiNode<N>, NodeError> {
+        if name.borrow() == "." { return Ok(self) }
         if let Ok(n) = self.make_ui_node(name.borrow()) {
             return self.obtain_node_mut(n)
         }
-        self.obtain_node_mut(name)
+        let node = self.obtain_node_mut(name)?;
+        if node.data.is_none() { node.add_data(NodeData::default()); }
+        Ok(node)
     }
     fn borrow_or_create_ui_node(&mut self, path: impl Borrow<str>) -> Result<&UiNode<N>, NodeError> {
         match path.borrow().split_once('/') {
@@ -105,6 +127,54 @@ impl <N: Default + Component> UiNodeCreationTrait<N> for UiNode<N> {
         }
     }
 }
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeCreationTrait<N> for UiArena<M, N> {
+    fn make_ui_node(&mut self, name: impl Borrow<str>) -> Result<String, NodeError> {
+        let id = self.make_node(name)?;
+        let node = self.get_mut(id)?;
+        node.data = Some(NodeData::default());
+        Ok(node.get_name().to_owned())
+    }
+    fn create_ui_node(&mut self, path: impl Borrow<str>) -> Result<String, NodeError> {
+        match path.borrow().rsplit_once('/') {
+            None => self.make_ui_node(path),
+            Some((rempath, _)) => {
+                self.borrow_or_create_ui_node_mut(rempath)?;
+                let mut node = UiNode::new();
+                node.add_data(NodeData::default());
+                let id = self.insert_node(path, node)?;
+                Ok(self.get(id)?.get_name().to_owned())
+            },
+        }
+    }
+    fn obtain_or_create_ui_node(&mut self, name: impl Borrow<str>) -> Result<&ArenaNode<NodeData<N>>, NodeError> {
+        self.obtain_or_create_ui_node_mut(name).map(|node| &*node)
+    }
+    fn obtain_or_create_ui_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut ArenaNode<NodeData<N>>, NodeError> {
+        if name.borrow() == "." { let root = self.root(); return self.get_mut(root) }
+        let node = self.obtain_or_create_node_mut(name)?;
+        if node.data.is_none() { node.data = Some(NodeData::default()) }
+        Ok(node)
+    }
+    fn borrow_or_create_ui_node(&mut self, path: impl Borrow<str>) -> Result<&ArenaNode<NodeData<N>>, NodeError> {
+        self.borrow_or_create_ui_node_mut(path).map(|node| &*node)
+    }
+    fn borrow_or_create_ui_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut ArenaNode<NodeData<N>>, NodeError> {
+        match path.borrow().rsplit_once('/') {
+            None => self.obtain_or_create_ui_node_mut(path),
+            Some((rempath, _)) => {
+                self.borrow_or_create_ui_node_mut(rempath)?;
+                let node = self.borrow_or_create_node_mut(path)?;
+                if node.data.is_none() { node.data = Some(NodeData::default()) }
+                Ok(node)
+            },
+        }
+    }
+}
+
+/// Storage of [`NodeData`] the tailored features like [`UiBuilder`], [`BuildAsNode`] and [`crate::UiTemplate`] work on.
+/// Implemented for both [`UiTree`] and [`UiArena`].
+pub trait UiStorageTrait<N: Default + UiComponent>: UiNodeCreationTrait<N> + NodeGeneralTrait<NodeData<N>, Handle = NodeId> + NodeDataTrait<NodeData<N>> + NodeHandleTrait<NodeData<N>> {}
+impl <N: Default + UiComponent, S: UiNodeCreationTrait<N> + NodeGeneralTrait<NodeData<N>, Handle = NodeId> + NodeDataTrait<NodeData<N>> + NodeHandleTrait<NodeData<N>>> UiStorageTrait<N> for S {}
 
 /// Trait that abstracts over [`NodeDataTrait`] to provide tailored
 /// implementations for the primitive in layouting context.
@@ -112,81 +182,73 @@ pub trait UiNodeDataTrait<N> {
     /// Adds new data to this node and returns the previous data.
     /// ## 📌 Note
     /// * Use [`UiNodeDataTrait::insert_ui_data`] for hierarchy insert `(supports path recursion)`
-    /// ## ⚠️ Panics
-    /// * Panics if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
-    /// Wont happen unless somebody messed with internals using elevated access methods _(not in prelude)_.
-    fn add_ui_data(&mut self, data: N) -> Option<N>;
+    /// ## ⚠️ Warning
+    /// * Returns [`NodeError::NoData`] if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
+    fn add_ui_data(&mut self, data: N) -> Result<Option<N>, NodeError>;
     /// ## 🚸 Recursive
     /// Inserts new data to this node or any other subnode and returns the previous data.
     /// ## 📌 Note
     /// * Use [`UiNodeDataTrait::add_ui_data`] for direct insert on this node `(no recursion)`
-    /// ## ⚠️ Panics
-    /// * Panics if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
-    /// Wont happen unless somebody messed with internals using elevated access methods _(not in prelude)_.
+    /// ## ⚠️ Warning
+    /// * Returns [`NodeError::NoData`] if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
     fn insert_ui_data(&mut self, path: impl Borrow<str>, data: N) -> Result<Option<N>, NodeError>;
     /// Removes data from this node and returns them.
     /// ## 📌 Note
     /// * Use [`UiNodeDataTrait::remove_ui_data`] for hierarchy retrieval `(supports path recursion)`
-    /// ## ⚠️ Panics
-    /// * Panics if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
-    /// Wont happen unless somebody messed with internals using elevated access methods _(not in prelude)_.
-    fn take_ui_data(&mut self) -> Option<N>;
+    /// ## ⚠️ Warning
+    /// * Returns [`NodeError::NoData`] if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
+    fn take_ui_data(&mut self) -> Result<Option<N>, NodeError>;
     /// ## 🚸 Recursive
     /// Removes data from this node or any other subnode and returns them.
     /// ## 📌 Note
     /// * Use [`UiNodeDataTrait::take_ui_data`] for direct retrieval on this node `(no recursion)`
-    /// ## ⚠️ Panics
-    /// * Panics if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
-    /// Wont happen unless somebody messed with internals using elevated access methods _(not in prelude)_.
+    /// ## ⚠️ Warning
+    /// * Returns [`NodeError::NoData`] if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
     fn remove_ui_data(&mut self, path: impl Borrow<str>) -> Result<Option<N>, NodeError>;
     /// Borrows data from this node.
     /// ## 📌 Note
     /// * Use [`UiNodeDataTrait::borrow_ui_data`] for hierarchy retrieval `(supports path recursion)`
-    /// ## ⚠️ Panics
-    /// * Panics if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
-    /// Wont happen unless somebody messed with internals using elevated access methods _(not in prelude)_.
-    fn obtain_ui_data(&self) -> Option<&N>;
+    /// ## ⚠️ Warning
+    /// * Returns [`NodeError::NoData`] if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
+    fn obtain_ui_data(&self) -> Result<Option<&N>, NodeError>;
     /// Borrows data from this node as mut.
     /// ## 📌 Note
     /// * Use [`UiNodeDataTrait::borrow_ui_data_mut`] for hierarchy retrieval `(supports path recursion)`
-    /// ## ⚠️ Panics
-    /// * Panics if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
-    /// Wont happen unless somebody messed with internals using elevated access methods _(not in prelude)_.
-    fn obtain_ui_data_mut(&mut self) -> Option<&mut N>;
+    /// ## ⚠️ Warning
+    /// * Returns [`NodeError::NoData`] if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
+    fn obtain_ui_data_mut(&mut self) -> Result<Option<&mut N>, NodeError>;
     /// ## 🚸 Recursive
     /// Borrows data from this node or any other subnode.
     /// ## 📌 Note
     /// * Use [`UiNodeDataTrait::obtain_ui_data`] for direct retrieval on this node `(no recursion)`
-    /// ## ⚠️ Panics
-    /// * Panics if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
-    /// Wont happen unless somebody messed with internals using elevated access methods _(not in prelude)_.
+    /// ## ⚠️ Warning
+    /// * Returns [`NodeError::NoData`] if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
     fn borrow_ui_data(&self, path: impl Borrow<str>) -> Result<Option<&N>, NodeError>;
     /// ## 🚸 Recursive
     /// Borrows data from this node or any other subnode as mut.
     /// ## 📌 Note
     /// * Use [`UiNodeDataTrait::obtain_ui_data_mut`] for direct retrieval on this node `(no recursion)`
-    /// ## ⚠️ Panics
-    /// * Panics if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
-    /// Wont happen unless somebody messed with internals using elevated access methods _(not in prelude)_.
+    /// ## ⚠️ Warning
+    /// * Returns [`NodeError::NoData`] if [`UiNode`] is missing [`NodeData`] struct that holds layout data + user data.
     fn borrow_ui_data_mut(&mut self, path: impl Borrow<str>) -> Result<Option<&mut N>, NodeError>;
 }
-impl <M: Default + Component, N: Default + Component> UiNodeDataTrait<N> for UiTree<M, N> {
-    fn add_ui_data(&mut self, data: N) -> Option<N> {
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeDataTrait<N> for UiTree<M, N> {
+    fn add_ui_data(&mut self, data: N) -> Result<Option<N>, NodeError> {
         self.node.add_ui_data(data)
     }
     fn insert_ui_data(&mut self, path: impl Borrow<str>, data: N) -> Result<Option<N>, NodeError> {
         self.node.insert_ui_data(path, data)
     }
-    fn take_ui_data(&mut self) -> Option<N> {
+    fn take_ui_data(&mut self) -> Result<Option<N>, NodeError> {
         self.node.take_ui_data()
     }
     fn remove_ui_data(&mut self, path: impl Borrow<str>) -> Result<Option<N>, NodeError> {
         self.node.remove_ui_data(path)
     }
-    fn obtain_ui_data(&self) -> Option<&N> {
+    fn obtain_ui_data(&self) -> Result<Option<&N>, NodeError> {
         self.node.obtain_ui_data()
     }
-    fn obtain_ui_data_mut(&mut self) -> Option<&mut N> {
+    fn obtain_ui_data_mut(&mut self) -> Result<Option<&mut N>, NodeError> {
         self.node.obtain_ui_data_mut()
     }
     fn borrow_ui_data(&self, path: impl Borrow<str>) -> Result<Option<&N>, NodeError> {
@@ -196,38 +258,34 @@ impl <M: Default + Component, N: Default + Component> UiNodeDataTrait<N> for UiT
         self.node.borrow_ui_data_mut(path)
     }
 }
-impl <N: Default + Component> UiNodeDataTrait<N> for UiNode<N> {
-    fn add_ui_data(&mut self, data: N) -> Option<N> {
-        let Some(container) = self.obtain_data_mut() else { panic!("This UiNode is missing Ui data!") };
-        core::mem::replace(&mut container.data, Some(data))
+impl <N: Default + UiComponent> UiNodeDataTrait<N> for UiNode<N> {
+    fn add_ui_data(&mut self, data: N) -> Result<Option<N>, NodeError> {
+        let Some(container) = self.data.as_mut() else { return Err(NodeError::NoData(self.get_path().to_owned())) };
+        Ok(core::mem::replace(&mut container.data, Some(data)))
     }
     fn insert_ui_data(&mut self, path: impl Borrow<str>, data: N) -> Result<Option<N>, NodeError> {
-        let Some(container) = self.borrow_data_mut(path)? else { panic!("This UiNode is missing Ui data!") };
-        Ok(core::mem::replace(&mut container.data, Some(data)))
+        self.borrow_node_mut(path)?.add_ui_data(data)
     }
-    fn take_ui_data(&mut self) -> Option<N> {
-        let Some(container) = self.obtain_data_mut() else { panic!("This UiNode is missing Ui data!") };
-        core::mem::replace(&mut container.data, None)
+    fn take_ui_data(&mut self) -> Result<Option<N>, NodeError> {
+        let Some(container) = self.data.as_mut() else { return Err(NodeError::NoData(self.get_path().to_owned())) };
+        Ok(core::mem::replace(&mut container.data, None))
     }
     fn remove_ui_data(&mut self, path: impl Borrow<str>) -> Result<Option<N>, NodeError> {
-        let Some(container) = self.borrow_data_mut(path)? else { panic!("This UiNode is missing Ui data!") };
-        Ok(core::mem::replace(&mut container.data, None))
+        self.borrow_node_mut(path)?.take_ui_data()
     }
-    fn obtain_ui_data(&self) -> Option<&N> {
-        let Some(container) = self.obtain_data() else { panic!("This UiNode is missing Ui data!") };
-        container.data.as_ref()
+    fn obtain_ui_data(&self) -> Result<Option<&N>, NodeError> {
+        let Some(container) = self.data.as_ref() else { return Err(NodeError::NoData(self.get_path().to_owned())) };
+        Ok(container.data.as_ref())
     }
-    fn obtain_ui_data_mut(&mut self) -> Option<&mut N> {
-        let Some(container) = self.obtain_data_mut() else { panic!("This UiNode is missing Ui data!") };
-        container.data.as_mut()
+    fn obtain_ui_data_mut(&mut self) -> Result<Option<&mut N>, NodeError> {
+        if self.data.is_none() { return Err(NodeError::NoData(self.get_path().to_owned())) }
+        Ok(self.data.as_mut().and_then(|container| container.data.as_mut()))
     }
     fn borrow_ui_data(&self, path: impl Borrow<str>) -> Result<Option<&N>, NodeError> {
-        let Some(container) = self.borrow_data(path)? else { panic!("This UiNode is missing Ui data!") };
-        Ok(container.data.as_ref())
+        self.borrow_node(path)?.obtain_ui_data()
     }
     fn borrow_ui_data_mut(&mut self, path: impl Borrow<str>) -> Result<Option<&mut N>, NodeError> {
-        let Some(container) = self.borrow_data_mut(path)? else { panic!("This UiNode is missing Ui data!") };
-        Ok(container.data.as_mut())
+        self.borrow_node_mut(path)?.obtain_ui_data_mut()
     }
 }
 
@@ -237,7 +295,7 @@ pub trait UiNodeTreeInitTrait {
     /// Creates new [`UiTree`].
     fn new(name: impl Borrow<str>) -> Self;
 }
-impl <M: Default + Component, N: Default + Component> UiNodeTreeInitTrait for UiTree<M, N> {
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeInitTrait for UiTree<M, N> {
     fn new(name: impl Borrow<str>) -> Self {
         let mut tree: UiTree<M, N> = NodeTreeInitTrait::new(name);
         tree.add_topdata(MasterData::default());
@@ -245,17 +303,66 @@ impl <M: Default + Component, N: Default + Component> UiNodeTreeInitTrait for Ui
         tree
     }
 }
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeInitTrait for UiArena<M, N> {
+    fn new(name: impl Borrow<str>) -> Self {
+        let mut tree: UiArena<M, N> = NodeTreeInitTrait::new(name);
+        tree.add_topdata(MasterData::default());
+        tree.add_data(NodeData::default());
+        tree
+    }
+}
 
 
 // #=======================#
 // #=== TAILORED TRAITS ===#
 
+/// Minimal number of nodes a subtree must have to be computed in parallel with its sibling subtrees.
+/// Smaller subtrees are always computed serially, because spawning tasks would cost more than it saves.
+/// ## 📌 Note
+/// * Only used with the `parallel` feature
+pub const PARALLEL_THRESHOLD: usize = 512;
+
 /// Trait with [`UiTree`] layout computation methods.
 pub trait UiNodeTreeComputeTrait {
     /// Compute the layout of the [`UiTree`].
+    /// ## 📌 Note
+    /// * With the `parallel` feature, subtrees of [`UiTree`] with at least [`PARALLEL_THRESHOLD`] nodes
+    ///   are computed on the `rayon` thread pool. The result is identical to the serial computation.
     fn compute(&mut self, parent: Rectangle3D);
+    /// Same as [`UiNodeTreeComputeTrait::compute`], but checks the tree with [`UiNodeTreeValidateTrait::validate`] first
+    /// and returns the first problem as an error instead of computing invalid rectangles.
+    /// ## 📌 Note
+    /// * Warnings are returned as errors too, the tree is computed only if it is fully valid
+    /// * Rectangles are checked after computing, if any is not finite the previous layout state is restored
+    fn try_compute(&mut self, parent: Rectangle3D) -> Result<(), UiError>;
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeComputeTrait for UiTree<M, N> {
+    fn compute(&mut self, parent: Rectangle3D) {
+
+        let mut abs_scale = 1.0;
+        let mut font_size = 16.0;
+
+        if let Some(master_data) = self.obtain_topdata() {
+            abs_scale = master_data.abs_scale;
+            font_size = master_data.font_size;
+        }
+
+        #[cfg(feature = "parallel")]
+        let springs = {
+            let mut sizes = Vec::new();
+            self.node.subtree_sizes(&mut sizes);
+            self.node.compute_all_parallel(parent, parent, abs_scale, font_size, &sizes)
+        };
+        #[cfg(not(feature = "parallel"))]
+        let springs = self.node.compute_all(parent, parent, abs_scale, font_size);
+
+        if springs { self.node.present(parent, parent) }
+    }
+    fn try_compute(&mut self, parent: Rectangle3D) -> Result<(), UiError> {
+        try_compute(self, parent)
+    }
 }
-impl <M: Default + Component, N: Default + Component> UiNodeTreeComputeTrait for UiTree<M, N> {
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeComputeTrait for UiArena<M, N> {
     fn compute(&mut self, parent: Rectangle3D) {
 
         let mut abs_scale = 1.0;
@@ -266,60 +373,487 @@ impl <M: Default + Component, N: Default + Component> UiNodeTreeComputeTrait for
             font_size = master_data.font_size;
         }
 
-        self.node.compute_all(parent, abs_scale, font_size);
+        let mut cursor = self.cursor();
+        if cursor.compute_all(parent, parent, abs_scale, font_size) { cursor.present(parent, parent) }
+    }
+    fn try_compute(&mut self, parent: Rectangle3D) -> Result<(), UiError> {
+        try_compute(self, parent)
+    }
+}
+
+/// Validates the tree, computes it and checks the computed rectangles.
+/// The previous layout state is restored if the computation produced rectangles that are not finite.
+fn try_compute<N: Default + UiComponent, T: UiNodeTreeComputeTrait + UiNodeTreeValidateTrait + UiCursorRootTrait<N>>(tree: &mut T, parent: Rectangle3D) -> Result<(), UiError> {
+    if !(parent.pos.is_finite() && parent.size.is_finite()) { return Err(UiError::InvalidSize(String::new())) }
+
+    // Rectangles from the last computation don't matter yet
+    if let Some(diagnostic) = tree.validate().into_iter().find(|diagnostic| diagnostic.kind != UiDiagnosticKind::NonFiniteRectangle) { return Err(diagnostic.into()) }
+
+    let mut states = Vec::new();
+    save_computed(&mut tree.cursor(), &mut states);
+    tree.compute(parent);
+    let Some(path) = find_non_finite(&mut tree.cursor(), "") else { return Ok(()) };
+    restore_computed(&mut tree.cursor(), &mut states.into_iter());
+    Err(UiDiagnostic { path, kind: UiDiagnosticKind::NonFiniteRectangle }.into())
+}
+/// Pushes the layout state of the node and all subnodes in depth-first order.
+fn save_computed<N: Default + UiComponent>(node: &mut impl UiCursorMutTrait<N>, states: &mut Vec<Option<ComputedState>>) {
+    states.push(node.data().map(|data| ComputedState { rectangle: data.rectangle, presented: data.presented, transition: data.transition, spring: data.spring }));
+    for index in 0..node.len() {
+        if let Some(mut subnode) = node.subnode(index) { save_computed(&mut subnode, states) }
+    }
+}
+/// Restores the layout state of the node and all subnodes saved by [`save_computed`].
+fn restore_computed<N: Default + UiComponent>(node: &mut impl UiCursorMutTrait<N>, states: &mut impl Iterator<Item = Option<ComputedState>>) {
+    if let (Some(data), Some(Some(state))) = (node.data(), states.next()) {
+        data.rectangle = state.rectangle;
+        data.presented = state.presented;
+        data.transition = state.transition;
+        data.spring = state.spring;
+    }
+    for index in 0..node.len() {
+        if let Some(mut subnode) = node.subnode(index) { restore_computed(&mut subnode, states) }
+    }
+}
+/// Returns the path of the first subnode with a computed rectangle that is not finite.
+fn find_non_finite<N: Default + UiComponent>(node: &mut impl UiCursorMutTrait<N>, path: &str) -> Option<String> {
+    for index in 0..node.len() {
+        let Some(mut subnode) = node.subnode(index) else { continue };
+        let path = if path.is_empty() { subnode.name().to_owned() } else { format!("{}/{}", path, subnode.name()) };
+        if let Some(data) = subnode.data() {
+            if !(data.rectangle.pos.is_finite() && data.rectangle.size.is_finite()) { return Some(path) }
+        }
+        if let Some(path) = find_non_finite(&mut subnode, &path) { return Some(path) }
+    }
+    None
+}
+
+/// Trait with [`UiTree`] comparison methods.
+pub trait UiNodeTreeDiffTrait<N> {
+    /// Compares this (old) tree against the new one and returns all structural and layout differences.
+    /// Computed rectangles are reported only if any of their components differ by more than `epsilon`.
+    /// ## 📌 Note
+    /// * User data is not compared, use [`UiNodeTreeDiffTrait::diff_with_data`] for that
+    /// * Moved nodes are matched by [`NodeId`] if both have one, otherwise by name if it is unique among the moved nodes
+    /// * Missing [`NodeData`] is compared as if it was default
+    fn diff(&self, new: &Self, epsilon: f32) -> UiDiff;
+    /// Same as [`UiNodeTreeDiffTrait::diff`], but also reports changed user data.
+    fn diff_with_data(&self, new: &Self, epsilon: f32) -> UiDiff where N: PartialEq;
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeDiffTrait<N> for UiTree<M, N> {
+    fn diff(&self, new: &Self, epsilon: f32) -> UiDiff {
+        self.node.diff(&new.node, epsilon)
+    }
+    fn diff_with_data(&self, new: &Self, epsilon: f32) -> UiDiff where N: PartialEq {
+        self.node.diff_with_data(&new.node, epsilon)
+    }
+}
+impl <N: Default + UiComponent> UiNodeTreeDiffTrait<N> for UiNode<N> {
+    fn diff(&self, new: &Self, epsilon: f32) -> UiDiff {
+        diff_subnodes(self, new, epsilon, |_, _| true)
+    }
+    fn diff_with_data(&self, new: &Self, epsilon: f32) -> UiDiff where N: PartialEq {
+        diff_subnodes(self, new, epsilon, |old, new| old == new)
+    }
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeDiffTrait<N> for UiArena<M, N> {
+    fn diff(&self, new: &Self, epsilon: f32) -> UiDiff {
+        diff_subnodes(ArenaCursor::new(self), ArenaCursor::new(new), epsilon, |_, _| true)
+    }
+    fn diff_with_data(&self, new: &Self, epsilon: f32) -> UiDiff where N: PartialEq {
+        diff_subnodes(ArenaCursor::new(self), ArenaCursor::new(new), epsilon, |old, new| old == new)
+    }
+}
+
+/// Diffs all subnodes of the two nodes. User data is compared with the provided comparator.
+fn diff_subnodes<'a, N: Default + UiComponent + 'a, C: UiCursorTrait<'a, N>>(old: C, new: C, epsilon: f32, data_eq: impl Fn(&Option<N>, &Option<N>) -> bool) -> UiDiff {
+    let mut old_nodes: HashMap<String, C> = HashMap::new();
+    let mut new_nodes: HashMap<String, C> = HashMap::new();
+    collect_subnodes(old, "", &mut old_nodes);
+    collect_subnodes(new, "", &mut new_nodes);
+    let parent = |path: &str| path.rsplit_once('/').map(|(parent, _)| parent.to_owned()).unwrap_or_default();
+    let name = |path: &str| path.rsplit_once('/').map(|(_, name)| name.to_owned()).unwrap_or(path.to_owned());
+
+    // Top-most nodes missing on the other side are candidates for moves
+    let removed: Vec<&String> = old_nodes.keys().filter(|path| !new_nodes.contains_key(*path) && { let p = parent(path); p.is_empty() || new_nodes.contains_key(&p) }).collect();
+    let added: Vec<&String> = new_nodes.keys().filter(|path| !old_nodes.contains_key(*path) && { let p = parent(path); p.is_empty() || old_nodes.contains_key(&p) }).collect();
+
+    let mut moves: Vec<(String, String)> = Vec::new();
+    for from in &removed {
+        let id = old_nodes[*from].id();
+        let to = match added.iter().find(|to| id.is_some() && new_nodes[**to].id() == id) {
+            Some(to) => Some(to),
+            None => {
+                let n = name(from);
+                let unique = removed.iter().filter(|path| name(path) == n).count() == 1;
+                let mut candidates = added.iter().filter(|path| name(path) == n);
+                match (unique, candidates.next(), candidates.next()) {
+                    (true, Some(to), None) if id.is_none() || new_nodes[*to].id().is_none() => Some(to),
+                    _ => None,
+                }
+            },
+        };
+        if let Some(to) = to {
+            if moves.iter().all(|(_, other)| other != *to) { moves.push(((*from).clone(), (*to).clone())); }
+        }
+    }
+
+    // Translates old paths into new paths
+    let target = |path: &str| -> String {
+        for (from, to) in &moves {
+            if path == from { return to.clone() }
+            if let Some(rest) = path.strip_prefix(&format!("{}/", from)) { return format!("{}/{}", to, rest) }
+        }
+        path.to_owned()
+    };
+
+    let mut diff = UiDiff::default();
+    for (from, to) in &moves {
+        diff.changes.push(UiChange::Moved { from: from.clone(), to: to.clone() });
+    }
+
+    let default = NodeData::<N>::default();
+    let mut matched: std::collections::HashSet<String> = std::collections::HashSet::new();
+    for (path, old_node) in &old_nodes {
+        let new_path = target(path);
+        let Some(new_node) = new_nodes.get(&new_path) else {
+            let p = parent(&new_path);
+            if p.is_empty() || new_nodes.contains_key(&p) { diff.changes.push(UiChange::Removed(path.clone())); }
+            continue;
+        };
+
+        let old_data = old_node.data().unwrap_or(&default);
+        let new_data = new_node.data().unwrap_or(&default);
+        if old_data.layout != new_data.layout {
+            diff.changes.push(UiChange::Layout { path: new_path.clone(), old: Box::new(old_data.layout), new: Box::new(new_data.layout) });
+        }
+        if old_data.stack != new_data.stack {
+            diff.changes.push(UiChange::Stack { path: new_path.clone(), old: old_data.stack, new: new_data.stack });
+        }
+        if old_data.font_size != new_data.font_size {
+            diff.changes.push(UiChange::FontSize { path: new_path.clone(), old: old_data.font_size, new: new_data.font_size });
+        }
+        if !data_eq(&old_data.data, &new_data.data) {
+            diff.changes.push(UiChange::Data(new_path.clone()));
+        }
+        let (old_rect, new_rect) = (old_data.rectangle, new_data.rectangle);
+        if !old_rect.pos.abs_diff_eq(new_rect.pos, epsilon) || !old_rect.size.abs_diff_eq(new_rect.size, epsilon)
+        || (old_rect.roll - new_rect.roll).abs() > epsilon || (old_rect.yaw - new_rect.yaw).abs() > epsilon || (old_rect.tilt - new_rect.tilt).abs() > epsilon {
+            diff.changes.push(UiChange::Rectangle { path: new_path.clone(), old: old_rect, new: new_rect });
+        }
+        matched.insert(new_path);
+    }
+
+    for path in new_nodes.keys() {
+        if matched.contains(path) { continue; }
+        let p = parent(path);
+        if p.is_empty() || matched.contains(&p) { diff.changes.push(UiChange::Added(path.clone())); }
+    }
+    diff
+}
+/// Collects all subnodes under their paths in depth-first order.
+fn collect_subnodes<'a, N: Default + UiComponent + 'a, C: UiCursorTrait<'a, N>>(node: C, path: &str, nodes: &mut HashMap<String, C>) {
+    for index in 0..node.len() {
+        let Some((name, subnode)) = node.subnode(index) else { continue };
+        let path = if path.is_empty() { name.to_owned() } else { format!("{}/{}", path, name) };
+        nodes.insert(path.clone(), subnode);
+        collect_subnodes(subnode, &path, nodes);
+    }
+}
+
+/// Trait with [`UiTree`] validation methods.
+pub trait UiNodeTreeValidateTrait {
+    /// Walks the tree and returns all problems that would make the computation skip nodes or produce invalid rectangles.
+    /// ## 📌 Note
+    /// * Static checks work before computing, [`UiDiagnosticKind::NonFiniteRectangle`] is reported only after computing
+    fn validate(&self) -> Vec<UiDiagnostic>;
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeValidateTrait for UiTree<M, N> {
+    fn validate(&self) -> Vec<UiDiagnostic> {
+        let mut diagnostics = Vec::new();
+        if let Some(master) = self.obtain_topdata() { master.validate(&mut diagnostics) }
+        validate_subnodes(&self.node, "", false, &mut diagnostics);
+        diagnostics
+    }
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> UiNodeTreeValidateTrait for UiArena<M, N> {
+    fn validate(&self) -> Vec<UiDiagnostic> {
+        let mut diagnostics = Vec::new();
+        if let Some(master) = self.obtain_topdata() { master.validate(&mut diagnostics) }
+        validate_subnodes(ArenaCursor::new(self), "", false, &mut diagnostics);
+        diagnostics
+    }
+}
+/// Validates all subnodes. `laid_out` tells if this node has a layout that computes its subnodes.
+fn validate_subnodes<'a, N: Default + UiComponent + 'a>(node: impl UiCursorTrait<'a, N>, path: &str, laid_out: bool, diagnostics: &mut Vec<UiDiagnostic>) {
+    for index in 0..node.len() {
+        let Some((name, subnode)) = node.subnode(index) else { continue };
+        let path = if path.is_empty() { name.to_owned() } else { format!("{}/{}", path, name) };
+        let Some(data) = subnode.data() else {
+            diagnostics.push(UiDiagnostic { path, kind: UiDiagnosticKind::MissingData });
+            continue;
+        };
+        data.validate(&path, laid_out, diagnostics);
+
+        // Divs inside a div that is never computed are already covered by its diagnostic
+        validate_subnodes(subnode, &path, true, diagnostics);
+    }
+}
+impl <M: Default + UiComponent> MasterData<M> {
+    /// Reports invalid scale or font size.
+    fn validate(&self, diagnostics: &mut Vec<UiDiagnostic>) {
+        if !(self.abs_scale.is_finite() && self.abs_scale > 0.0 && self.font_size.is_finite() && self.font_size > 0.0) {
+            diagnostics.push(UiDiagnostic { path: String::new(), kind: UiDiagnosticKind::InvalidMasterData });
+        }
+    }
+}
+impl <N: Default + UiComponent> NodeData<N> {
+    /// Reports all problems of a single node. `laid_out` tells if the parent computes divs inside it.
+    fn validate(&self, path: &str, laid_out: bool, diagnostics: &mut Vec<UiDiagnostic>) {
+        let mut report = |kind| diagnostics.push(UiDiagnostic { path: path.to_owned(), kind });
+        match &self.layout {
+            Layout::Div(_) => if !laid_out { report(UiDiagnosticKind::ParametricRoot) },
+            Layout::Solid(solid) => {
+                let zero = |axis: usize| [solid.size.abs, solid.size.prc, solid.size.rem].iter().flatten().all(|size| size[axis] == 0.0);
+                if zero(0) || zero(1) { report(UiDiagnosticKind::ZeroSolidSize) }
+            },
+            Layout::Window(_) => {},
+        }
+        if let Some(size) = self.font_size {
+            if !(size.is_finite() && size > 0.0) { report(UiDiagnosticKind::InvalidFontSize(size)) }
+        }
+        if !(self.rectangle.pos.is_finite() && self.rectangle.size.is_finite()) { report(UiDiagnosticKind::NonFiniteRectangle) }
     }
 }
 
 /// Trait that [Layout] types implement so they can be build as new node.
 pub trait BuildAsNode {
-    /// Build the widget inside the [`UiTree`] at the given path.
-    fn build<M: Default + Component, N: Default + Component>(self, ui: &mut UiTree<M, N>, path: impl Borrow<str>) -> Result<String, NodeError> where Self: Sized;
+    /// Build the widget inside the [`UiTree`] or [`UiArena`] at the given path and returns the new nodes' name.
+    /// Missing nodes in the path are created with default [`NodeData`].
+    fn build<S: UiStorageTrait<N>, N: Default + UiComponent>(self, ui: &mut S, path: impl Borrow<str>) -> Result<String, NodeError> where Self: Sized;
+    /// Starts a [`UiBuilder`] with this layout, so data, stacking and subnodes can be set before building.
+    fn builder<'a, S: UiStorageTrait<N>, N: Default + UiComponent>(self) -> UiBuilder<'a, S, N> where Self: Sized + Into<Layout> {
+        UiBuilder::new(self)
+    }
 }
 impl BuildAsNode for layout::Window {
-    fn build<M: Default + Component, N: Default + Component>(self, ui: &mut UiTree<M, N>, path: impl Borrow<str>) -> Result<String, NodeError> where Self: Sized {
-        ui.create_node(path.borrow())?;
-        let mut container: NodeData<N> = NodeData::new();
-        container.layout = self.into();
-        ui.insert_data(path, container)?;
-        Ok(String::new())
+    fn build<S: UiStorageTrait<N>, N: Default + UiComponent>(self, ui: &mut S, path: impl Borrow<str>) -> Result<String, NodeError> where Self: Sized {
+        build_node(ui, path.borrow(), self.into())
     }
 }
 impl BuildAsNode for layout::Solid {
-    fn build<M: Default + Component, N: Default + Component>(self, ui: &mut UiTree<M, N>, path: impl Borrow<str>) -> Result<String, NodeError> where Self: Sized {
-        ui.create_node(path.borrow())?;
-        let mut container: NodeData<N> = NodeData::new();
-        container.layout = self.into();
-        ui.insert_data(path, container)?;
-        Ok(String::new())
+    fn build<S: UiStorageTrait<N>, N: Default + UiComponent>(self, ui: &mut S, path: impl Borrow<str>) -> Result<String, NodeError> where Self: Sized {
+        build_node(ui, path.borrow(), self.into())
     }
 }
 impl BuildAsNode for layout::Div {
-    fn build<M: Default + Component, N: Default + Component>(self, ui: &mut UiTree<M, N>, path: impl Borrow<str>) -> Result<String, NodeError> where Self: Sized {
-        ui.create_node(path.borrow())?;
-        let mut container: NodeData<N> = NodeData::new();
-        container.layout = self.into();
-        ui.insert_data(path, container)?;
-        Ok(String::new())
+    fn build<S: UiStorageTrait<N>, N: Default + UiComponent>(self, ui: &mut S, path: impl Borrow<str>) -> Result<String, NodeError> where Self: Sized {
+        build_node(ui, path.borrow(), self.into())
     }
 }
 
 
+/// Creates the node with default [`NodeData`] and sets its layout.
+fn build_node<S: UiStorageTrait<N>, N: Default + UiComponent>(ui: &mut S, path: &str, layout: Layout) -> Result<String, NodeError> {
+    let name = ui.create_ui_node(path)?;
+    let path = match path.rsplit_once('/') {
+        Some((rempath, _)) => format!("{}/{}", rempath, name),
+        None => name.clone(),
+    };
+    if let Some(container) = ui.borrow_data_mut(path)? { container.layout = layout }
+    Ok(name)
+}
+
+
 // #============================#
 // #=== PRIVATE INNER TRAITS ===#
 
-/// Trait with [`UiNode`] layout computation methods. Includes private methods.
-trait UiNodeComputeTrait {
-    fn compute_all(&mut self, parent: Rectangle3D, abs_scale: f32, font_size: f32);
-    fn compute_content(&mut self, ancestor_size: Vec2, ancestor_padding: Vec4, abs_scale: f32, font_size: f32) -> Vec2;
-    fn compute_stack(&mut self, ancestor_size: Vec2, ancestor_padding: Vec4, abs_scale: f32, font_size: f32, horizontal: bool) -> Vec2;
-    fn align_stack(&mut self, ancestor_position: Vec2);
+/// Mutable access to a node and its subnodes, so the layout is computed the same way on every storage.
+/// Implemented for [`UiNode`] and for nodes of [`UiArena`].
+pub(crate) trait UiCursorMutTrait<N: Default + UiComponent> {
+    /// Cursor pointing at a subnode.
+    type Subnode<'a>: UiCursorMutTrait<N> where Self: 'a;
+    /// Returns name of the node.
+    fn name(&self) -> &str;
+    /// Returns depth of the node within the hierarchy.
+    fn depth(&self) -> f32;
+    /// Borrows the data of the node as mut.
+    fn data(&mut self) -> Option<&mut NodeData<N>>;
+    /// Returns the number of subnodes.
+    fn len(&self) -> usize;
+    /// Returns cursor pointing at the subnode at the index.
+    fn subnode(&mut self, index: usize) -> Option<Self::Subnode<'_>>;
+}
+impl <N: Default + UiComponent> UiCursorMutTrait<N> for UiNode<N> {
+    type Subnode<'a> = &'a mut UiNode<N> where Self: 'a;
+    fn name(&self) -> &str {
+        self.get_name()
+    }
+    fn depth(&self) -> f32 {
+        self.get_depth()
+    }
+    fn data(&mut self) -> Option<&mut NodeData<N>> {
+        self.data.as_mut()
+    }
+    fn len(&self) -> usize {
+        self.nodes.len()
+    }
+    fn subnode(&mut self, index: usize) -> Option<&mut UiNode<N>> {
+        self.nodes.get_index_mut(index).map(|(_, subnode)| subnode)
+    }
+}
+impl <N: Default + UiComponent, T: UiCursorMutTrait<N> + ?Sized> UiCursorMutTrait<N> for &mut T {
+    type Subnode<'a> = T::Subnode<'a> where Self: 'a;
+    fn name(&self) -> &str {
+        (**self).name()
+    }
+    fn depth(&self) -> f32 {
+        (**self).depth()
+    }
+    fn data(&mut self) -> Option<&mut NodeData<N>> {
+        (**self).data()
+    }
+    fn len(&self) -> usize {
+        (**self).len()
+    }
+    fn subnode(&mut self, index: usize) -> Option<T::Subnode<'_>> {
+        (**self).subnode(index)
+    }
+}
+
+/// Mutable cursor pointing at a node of [`NodeArena`].
+pub(crate) struct ArenaCursorMut<'a, D, N: Default + UiComponent> {
+    arena: &'a mut NodeArena<D, NodeData<N>>,
+    id: NodeId,
+}
+impl <'a, D, N: Default + UiComponent> ArenaCursorMut<'a, D, N> {
+    /// Creates new cursor pointing at the root node.
+    pub(crate) fn new(arena: &'a mut NodeArena<D, NodeData<N>>) -> Self {
+        let id = arena.root();
+        ArenaCursorMut { arena, id }
+    }
+}
+impl <'c, D, N: Default + UiComponent> UiCursorMutTrait<N> for ArenaCursorMut<'c, D, N> {
+    type Subnode<'a> = ArenaCursorMut<'a, D, N> where Self: 'a;
+    fn name(&self) -> &str {
+        self.arena.get(self.id).map(|node| node.get_name().as_str()).unwrap_or_default()
+    }
+    fn depth(&self) -> f32 {
+        self.arena.get(self.id).map(|node| node.get_depth()).unwrap_or_default()
+    }
+    fn data(&mut self) -> Option<&mut NodeData<N>> {
+        self.arena.get_mut(self.id).ok()?.data.as_mut()
+    }
+    fn len(&self) -> usize {
+        self.arena.get(self.id).map(|node| node.get_subnodes().len()).unwrap_or_default()
+    }
+    fn subnode(&mut self, index: usize) -> Option<ArenaCursorMut<'_, D, N>> {
+        let id = *self.arena.get(self.id).ok()?.get_subnodes().get_index(index)?.1;
+        Some(ArenaCursorMut { arena: &mut *self.arena, id })
+    }
 }
-impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> { 
-    /// Triggers the recursion in the right manner.
-    fn compute_all(&mut self, parent: Rectangle3D, abs_scale: f32, mut font_size: f32) {
+
+/// Trait giving [`UiCursorMutTrait`] pointing at the root node of the storage.
+pub(crate) trait UiCursorRootTrait<N: Default + UiComponent> {
+    /// Cursor pointing at the root node.
+    type Cursor<'a>: UiCursorMutTrait<N> where Self: 'a;
+    /// Returns cursor pointing at the root node.
+    fn cursor(&mut self) -> Self::Cursor<'_>;
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> UiCursorRootTrait<N> for UiTree<M, N> {
+    type Cursor<'a> = &'a mut UiNode<N> where Self: 'a;
+    fn cursor(&mut self) -> &mut UiNode<N> {
+        &mut self.node
+    }
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> UiCursorRootTrait<N> for UiArena<M, N> {
+    type Cursor<'a> = ArenaCursorMut<'a, MasterData<M>, N> where Self: 'a;
+    fn cursor(&mut self) -> ArenaCursorMut<'_, MasterData<M>, N> {
+        ArenaCursorMut::new(self)
+    }
+}
+
+/// Shared access to a node and its subnodes, so the tree is validated and compared the same way on every storage.
+/// Implemented for [`UiNode`] and for nodes of [`UiArena`].
+pub(crate) trait UiCursorTrait<'a, N: Default + UiComponent>: Copy {
+    /// Borrows the data of the node.
+    fn data(self) -> Option<&'a NodeData<N>>;
+    /// Returns id of the node if it has one.
+    fn id(self) -> Option<NodeId>;
+    /// Returns the number of subnodes.
+    fn len(self) -> usize;
+    /// Returns name of the subnode at the index and cursor pointing at it.
+    fn subnode(self, index: usize) -> Option<(&'a String, Self)>;
+}
+impl <'a, N: Default + UiComponent> UiCursorTrait<'a, N> for &'a UiNode<N> {
+    fn data(self) -> Option<&'a NodeData<N>> {
+        self.data.as_ref()
+    }
+    fn id(self) -> Option<NodeId> {
+        self.get_id()
+    }
+    fn len(self) -> usize {
+        self.nodes.len()
+    }
+    fn subnode(self, index: usize) -> Option<(&'a String, Self)> {
+        self.nodes.get_index(index)
+    }
+}
+
+/// Cursor pointing at a node of [`NodeArena`].
+pub(crate) struct ArenaCursor<'a, D, N: Default + UiComponent> {
+    arena: &'a NodeArena<D, NodeData<N>>,
+    id: NodeId,
+}
+impl <'a, D, N: Default + UiComponent> ArenaCursor<'a, D, N> {
+    /// Creates new cursor pointing at the root node.
+    pub(crate) fn new(arena: &'a NodeArena<D, NodeData<N>>) -> Self {
+        ArenaCursor { arena, id: arena.root() }
+    }
+}
+impl <D, N: Default + UiComponent> Clone for ArenaCursor<'_, D, N> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+impl <D, N: Default + UiComponent> Copy for ArenaCursor<'_, D, N> {}
+impl <'a, D, N: Default + UiComponent> UiCursorTrait<'a, N> for ArenaCursor<'a, D, N> {
+    fn data(self) -> Option<&'a NodeData<N>> {
+        self.arena.get(self.id).ok()?.data.as_ref()
+    }
+    fn id(self) -> Option<NodeId> {
+        Some(self.id)
+    }
+    fn len(self) -> usize {
+        self.arena.get(self.id).map(|node| node.get_subnodes().len()).unwrap_or_default()
+    }
+    fn subnode(self, index: usize) -> Option<(&'a String, Self)> {
+        let (name, id) = self.arena.get(self.id).ok()?.get_subnodes().get_index(index)?;
+        Some((name, ArenaCursor { arena: self.arena, id: *id }))
+    }
+}
+
+/// Trait with layout computation methods. Includes private methods.
+trait UiNodeComputeTrait<N: Default + UiComponent>: UiCursorMutTrait<N> {
+    /// Triggers the recursion in the right manner. The root rectangle is passed down for projecting [`crate::WorldAnchor`]s.
+    /// Returns `true` if any computed node has a spring, so the presented rectangles need [`UiNodePresentTrait::present`].
+    fn compute_all(&mut self, root: Rectangle3D, parent: Rectangle3D, abs_scale: f32, font_size: f32) -> bool {
+        let Some((my_rectangle, font_size)) = self.compute_node(root, parent, abs_scale, font_size) else { return false };
+        let mut springs = self.data().is_some_and(|data| data.spring.is_some());
+
+        // Enter recursion
+        for index in 0..self.len() {
+            if let Some(mut subnode) = self.subnode(index) {
+                springs |= subnode.compute_all(root, my_rectangle, abs_scale, font_size);
+            }
+        }
+        springs
+    }
+    /// Computes the layout of this node only. Returns the rectangle and font size for the subnodes to inherit.
+    fn compute_node(&mut self, root: Rectangle3D, parent: Rectangle3D, abs_scale: f32, mut font_size: f32) -> Option<(Rectangle3D, f32)> {
 
         // Get depth before mutating self
-        let depth = self.get_depth();
+        let depth = self.depth();
         
         let mut skip = true;
         let mut is_parametric = false;
@@ -327,7 +861,7 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
         // Check here if computation is required for partial recalculation
 
         // Compute my layout and return computed rectangle for recursion
-        let my_rectangle = if let Some(node_data) = &mut self.data {
+        let my_rectangle = if let Some(node_data) = self.data() {
 
             // Overwrite passed style with font size
             if let Some(fnt) = node_data.font_size { font_size = fnt }
@@ -338,7 +872,10 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
                     is_parametric = true;
                 },
                 Layout::Window(l) => {
-                    node_data.rectangle = l.compute(parent.into(), abs_scale, font_size).into();
+                    node_data.rectangle = match &node_data.anchor {
+                        Some(anchor) => l.compute_anchored(anchor, root.into(), parent.into(), abs_scale, font_size).into(),
+                        None => l.compute(parent.into(), abs_scale, font_size).into(),
+                    };
                     skip = false;
                 },
                 Layout::Solid(l)  => {
@@ -349,9 +886,17 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
 
             // Adding depth
             node_data.rectangle.pos.z = depth;
+
+            // Animate towards the computed rectangle
+            if let Some(transition) = &mut node_data.transition {
+                node_data.rectangle = transition.retarget(node_data.rectangle, parent);
+            }
+
+            // Without springs the presented rectangle is the computed one
+            node_data.presented = node_data.rectangle;
             node_data.rectangle
 
-        } else { return; };
+        } else { return None; };
 
         if skip == false {
             if is_parametric {
@@ -363,15 +908,12 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
             }
         }
 
-        // Enter recursion
-        for (_, subnode) in &mut self.nodes {
-            subnode.compute_all(my_rectangle, abs_scale, font_size);
-        }
+        Some((my_rectangle, font_size))
     }
     /// Computes the content only.
     fn compute_content(&mut self, ancestor_size: Vec2, ancestor_padding: Vec4, abs_scale: f32, font_size: f32) -> Vec2 {
 
-        let stack_options = self.data.as_ref().unwrap().stack;
+        let stack_options = self.data().unwrap().stack;
 
         match stack_options.direction {
             FlexDirection::Horizontal => self.compute_stack(ancestor_size, ancestor_padding, abs_scale, font_size, true),
@@ -382,17 +924,18 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
     /// Secondary pass after alignment of parent nodes is required.
     fn compute_stack(&mut self, ancestor_size: Vec2, ancestor_padding: Vec4, abs_scale: f32, font_size: f32, horizontal: bool) -> Vec2 {
 
-        let mut matrix: Vec<Vec<&mut Node<NodeData<N>>>> = Vec::new();
+        let mut matrix: Vec<Vec<usize>> = Vec::new();
         let mut content_size = Vec2::ZERO;
 
-        // Sort mutable pointers into matrix
+        // Sort subnode indices into matrix
         let mut i = 0;
         matrix.push(Vec::new());
-        for (_, subnode) in &mut self.nodes {
-            if let Some(subnode_data) = &subnode.data {
+        for index in 0..self.len() {
+            let Some(mut subnode) = self.subnode(index) else { continue };
+            if let Some(subnode_data) = subnode.data() {
                 if let Layout::Div(layout) = &subnode_data.layout {
                     let br = layout.force_break;
-                    matrix[i].push(subnode);
+                    matrix[i].push(index);
                     if br {
                         i += 1;
                         matrix.push(Vec::new());
@@ -404,8 +947,9 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
 
         // INSIDE MATRIX =================================================================
 
-        let gap = self.data.as_ref().unwrap().stack.gap.evaluate(abs_scale, ancestor_size, font_size);
-        let align = self.data.as_ref().unwrap().stack.node_alignment.0;
+        let stack = self.data().unwrap().stack;
+        let gap = stack.gap.evaluate(abs_scale, ancestor_size, font_size);
+        let align = stack.node_alignment.0;
 
 
         let mut line_cursor = if horizontal { ancestor_padding.y } else { ancestor_padding.x };
@@ -413,7 +957,7 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
         //--------------------------//
         let mut _i = 0;             //
         let _i_max = matrix.len();  //
-        for line in &mut matrix {   //
+        for line in &matrix {       //
             // INSIDE LINE =================================================================
 
             // Register that is shared between the two passes
@@ -425,11 +969,12 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
             // First pass to compute sizes--//
             let mut _ii = 0;                //
             let _ii_max = line.len();       //
-            for subnode in &mut *line {     //
+            for index in line {             //
                 // INSIDE SUBNODE =================================================================
+                let mut subnode = self.subnode(*index).unwrap();
 
                 // Fetch data
-                let subnode_data = subnode.data.as_ref().unwrap();
+                let subnode_data = subnode.data().unwrap();
                 let layout = if let Layout::Div(layout) = subnode_data.layout { layout } else { unreachable!() };
 
                 // Get padding & margin => compute range of motion
@@ -441,7 +986,7 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
                 let potential_content = subnode.compute_content(ancestor_size, padding, abs_scale, font_size);
 
                 // Fetch data again, because they were modified
-                let subnode_data = subnode.data.as_mut().unwrap();
+                let subnode_data = subnode.data().unwrap();
                 let mut subnode_content = subnode_data.content_size;
 
                 // Overwrite subnode content if div contains no subdivs
@@ -466,11 +1011,12 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
             // Second pass to align them----//
             let mut _ii = 0;                //
             let _ii_max = line.len();       //
-            for subnode in &mut *line {     //
+            for index in line {             //
                 // INSIDE SUBNODE =================================================================
+                let mut subnode = self.subnode(*index).unwrap();
 
                 // Fetch data
-                let subnode_data = subnode.data.as_ref().unwrap();
+                let subnode_data = subnode.data().unwrap();
                 let layout = if let Layout::Div(layout) = subnode_data.layout { layout } else { unreachable!() };
                 
                 let margin = comline.divs[_ii].margin;
@@ -503,17 +1049,14 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
                 };
                 
 
-                // Fetch data again, because they were modified
-                let subnode_data = subnode.data.as_mut().unwrap();
-
                 let _xxx = my_offset + if horizontal { Vec2::new(0.0, line_cursor) } else { Vec2::new(line_cursor, 0.0) };
                 subnode_data.rectangle.pos.x = _xxx.x;
                 subnode_data.rectangle.pos.y = _xxx.y;
                 subnode_data.rectangle.size = size;
 
 
-                let subnode_data = subnode.data.as_ref().unwrap();
-                subnode.align_stack(subnode_data.rectangle.pos.xy());
+                let position = subnode_data.rectangle.pos.xy();
+                subnode.align_stack(position);
 
 
                 // END OF INSIDE SUBNODE =================================================================
@@ -546,8 +1089,9 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
     /// This is the secondary pass to align the nodes.
     fn align_stack(&mut self, ancestor_position: Vec2) {
 
-        for (_, subnode) in &mut self.nodes {
-            if let Some(subnode_data) = &mut subnode.data {
+        for index in 0..self.len() {
+            let Some(mut subnode) = self.subnode(index) else { continue };
+            if let Some(subnode_data) = subnode.data() {
                 if let Layout::Div(_) = &subnode_data.layout {
 
                     subnode_data.rectangle.pos.x += ancestor_position.x;
@@ -560,6 +1104,52 @@ impl <N:Default + Component> UiNodeComputeTrait for UiNode<N> {
 
     }
 }
+impl <N: Default + UiComponent, T: UiCursorMutTrait<N> + ?Sized> UiNodeComputeTrait<N> for T {}
+
+#[cfg(any(feature = "parallel", test))]
+impl <N: Default + UiComponent> UiNode<N> {
+    /// Triggers the recursion in the right manner. Subtrees with at least [`PARALLEL_THRESHOLD`] nodes are computed in parallel.
+    /// `sizes` are the subtree sizes of this node from [`Node::subtree_sizes`], so nothing is counted twice.
+    /// Returns `true` if any computed node has a spring, same as [`UiNodeComputeTrait::compute_all`].
+    /// ## 📌 Note
+    /// * Without the `parallel` feature the large subtrees are computed serially too
+    fn compute_all_parallel(&mut self, root: Rectangle3D, parent: Rectangle3D, abs_scale: f32, font_size: f32, sizes: &[usize]) -> bool {
+        let Some((my_rectangle, font_size)) = self.compute_node(root, parent, abs_scale, font_size) else { return false };
+        let mut springs = self.data.as_ref().is_some_and(|data| data.spring.is_some());
+
+        // Subtrees depend only on the rectangle of this node, so they can be solved independently
+        let mut large = Vec::new();
+        let mut offset = 1;
+        for subnode in self.nodes.values_mut() {
+            let size = sizes[offset];
+            if size < PARALLEL_THRESHOLD {
+                springs |= subnode.compute_all(root, my_rectangle, abs_scale, font_size);
+            } else {
+                large.push((subnode, &sizes[offset..offset + size]));
+            }
+            offset += size;
+        }
+
+        #[cfg(feature = "parallel")]
+        {
+            use rayon::prelude::*;
+            springs |= large.into_par_iter().map(|(subnode, sizes)| subnode.compute_all_parallel(root, my_rectangle, abs_scale, font_size, sizes)).reduce(|| false, |a, b| a || b);
+        }
+        #[cfg(not(feature = "parallel"))]
+        for (subnode, sizes) in large {
+            springs |= subnode.compute_all_parallel(root, my_rectangle, abs_scale, font_size, sizes);
+        }
+        springs
+    }
+}
+
+/// Layout state of a node changed by the computation.
+struct ComputedState {
+    rectangle: Rectangle3D,
+    presented: Rectangle3D,
+    transition: Option<Transition>,
+    spring: Option<Spring>,
+}
 
 struct ComputedDiv {
     size: Vec2,
@@ -574,9 +1164,358 @@ struct ComputedLine {
 // #========================#
 // #=== EXTRACTOR TRAITS ===#
 
-// WORK IN PROGRESS!!!
-/// # WIP
-pub trait Extract <T> {
-    fn get_extract (&self) -> T;
-    fn set_extract (&mut self, val: T) -> T;
-}
\ No newline at end of file
+/// Trait for generic access to properties of a type by key. Used by animation and editor tooling.
+/// See [`LayoutProperty`] for the supported layout fields.
+pub trait Extract <K, T> {
+    /// Returns the value of the property or `None` if this type doesn't have it.
+    fn get_extract (&self, key: K) -> Option<T>;
+    /// Replaces the value of the property and returns the previous one.
+    /// Returns `None` and changes nothing if this type doesn't have the property or the value is of a different kind.
+    fn set_extract (&mut self, key: K, val: T) -> Option<T>;
+}
+impl Extract<LayoutProperty, LayoutValue> for layout::Window {
+    fn get_extract(&self, key: LayoutProperty) -> Option<LayoutValue> {
+        match key {
+            LayoutProperty::WindowPos => Some(self.pos.into()),
+            LayoutProperty::WindowSize => Some(self.size.into()),
+            _ => None,
+        }
+    }
+    fn set_extract(&mut self, key: LayoutProperty, val: LayoutValue) -> Option<LayoutValue> {
+        match (key, val) {
+            (LayoutProperty::WindowPos, LayoutValue::Vec2(val)) => Some(std::mem::replace(&mut self.pos, val).into()),
+            (LayoutProperty::WindowSize, LayoutValue::Vec2(val)) => Some(std::mem::replace(&mut self.size, val).into()),
+            _ => None,
+        }
+    }
+}
+impl Extract<LayoutProperty, LayoutValue> for layout::Solid {
+    fn get_extract(&self, key: LayoutProperty) -> Option<LayoutValue> {
+        match key {
+            LayoutProperty::SolidSize => Some(self.size.into()),
+            LayoutProperty::SolidAlignX => Some(self.align_x.into()),
+            LayoutProperty::SolidAlignY => Some(self.align_y.into()),
+            _ => None,
+        }
+    }
+    fn set_extract(&mut self, key: LayoutProperty, val: LayoutValue) -> Option<LayoutValue> {
+        match (key, val) {
+            (LayoutProperty::SolidSize, LayoutValue::Vec2(val)) => Some(std::mem::replace(&mut self.size, val).into()),
+            (LayoutProperty::SolidAlignX, LayoutValue::Align(val)) => Some(std::mem::replace(&mut self.align_x, val).into()),
+            (LayoutProperty::SolidAlignY, LayoutValue::Align(val)) => Some(std::mem::replace(&mut self.align_y, val).into()),
+            _ => None,
+        }
+    }
+}
+impl Extract<LayoutProperty, LayoutValue> for layout::Div {
+    fn get_extract(&self, key: LayoutProperty) -> Option<LayoutValue> {
+        match key {
+            LayoutProperty::DivPadding => Some(self.padding.into()),
+            LayoutProperty::DivMargin => Some(self.margin.into()),
+            _ => None,
+        }
+    }
+    fn set_extract(&mut self, key: LayoutProperty, val: LayoutValue) -> Option<LayoutValue> {
+        match (key, val) {
+            (LayoutProperty::DivPadding, LayoutValue::Vec4(val)) => Some(std::mem::replace(&mut self.padding, val).into()),
+            (LayoutProperty::DivMargin, LayoutValue::Vec4(val)) => Some(std::mem::replace(&mut self.margin, val).into()),
+            _ => None,
+        }
+    }
+}
+impl Extract<LayoutProperty, LayoutValue> for layout::FlexBox {
+    fn get_extract(&self, key: LayoutProperty) -> Option<LayoutValue> {
+        match key {
+            LayoutProperty::StackGap => Some(self.gap.into()),
+            _ => None,
+        }
+    }
+    fn set_extract(&mut self, key: LayoutProperty, val: LayoutValue) -> Option<LayoutValue> {
+        match (key, val) {
+            (LayoutProperty::StackGap, LayoutValue::Vec2(val)) => Some(std::mem::replace(&mut self.gap, val).into()),
+            _ => None,
+        }
+    }
+}
+impl Extract<LayoutProperty, LayoutValue> for Layout {
+    fn get_extract(&self, key: LayoutProperty) -> Option<LayoutValue> {
+        match self {
+            Layout::Window(layout) => layout.get_extract(key),
+            Layout::Solid(layout) => layout.get_extract(key),
+            Layout::Div(layout) => layout.get_extract(key),
+        }
+    }
+    fn set_extract(&mut self, key: LayoutProperty, val: LayoutValue) -> Option<LayoutValue> {
+        match self {
+            Layout::Window(layout) => layout.set_extract(key, val),
+            Layout::Solid(layout) => layout.set_extract(key, val),
+            Layout::Div(layout) => layout.set_extract(key, val),
+        }
+    }
+}
+impl <N: Default + UiComponent> Extract<LayoutProperty, LayoutValue> for NodeData<N> {
+    fn get_extract(&self, key: LayoutProperty) -> Option<LayoutValue> {
+        match key {
+            LayoutProperty::StackGap => self.stack.get_extract(key),
+            _ => self.layout.get_extract(key),
+        }
+    }
+    fn set_extract(&mut self, key: LayoutProperty, val: LayoutValue) -> Option<LayoutValue> {
+        match key {
+            LayoutProperty::StackGap => self.stack.set_extract(key, val),
+            _ => self.layout.set_extract(key, val),
+        }
+    }
+}
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use crate::prelude::*;
+    use crate::import::*;
+    use crate::{NodeDataTrait, NodeCreationTrait, NodeError, Window, Solid, Rectangle2D, UiError, WorldAnchor};
+
+    /// Builds a wide tree mixing all layout types.
+    fn sample_tree(width: usize) -> UiTree {
+        let mut tree: UiTree = UiTree::new("Root");
+        for i in 0..width {
+            let window = format!("w{}", i);
+            Window::new().pos(Prc(Vec2::splat(i as f32))).size(Prc(Vec2::splat(50.0))).build(&mut tree, window.as_str()).unwrap();
+            tree.borrow_node_mut(window.as_str()).unwrap().data.as_mut().unwrap().stack = FlexBox::new().gap(Abs(Vec2::splat(2.0)));
+            for j in 0..width {
+                let div = format!("{}/d{}", window, j);
+                let layout = if j % 3 == 2 { Div::new().pad(Abs(Vec4::splat(1.0))).br() } else { Div::new().margin(Abs(Vec4::splat(3.0))) };
+                layout.build(&mut tree, div.as_str()).unwrap();
+                tree.borrow_node_mut(div.as_str()).unwrap().data.as_mut().unwrap().content_size = Vec2::new(10.0, 5.0 + j as f32);
+                Solid::new().size(Abs(Vec2::new(2.0, 1.0))).build(&mut tree, format!("{}/s", div).as_str()).unwrap();
+            }
+        }
+        tree
+    }
+
+    #[test]
+    fn arena_compute () {
+        let mut tree = sample_tree(6);
+        let mut arena: UiArena = tree.clone().into();
+
+        let root = Rectangle2D::new().with_size((800.0, 600.0)).into();
+        tree.compute(root);
+        arena.compute(root);
+
+        for (path, _, node) in tree.iter_dfs() {
+            let expected = node.data.as_ref().unwrap().rectangle;
+            let computed = arena.borrow_data(path.as_str()).unwrap().unwrap().rectangle;
+            assert_eq!(expected, computed, "Mismatch at '{}'", path);
+        }
+    }
+
+    #[test]
+    fn arena_storage () {
+        let mut arena: UiArena<NoData, Tag> = UiArena::new("Root");
+        crate::ui!(&mut arena, {
+            "menu": Window::new().size(Abs(Vec2::splat(100.0))) => {
+                data: Tag(1),
+                "item": Div::new() => { content_size: (10.0, 10.0) },
+            },
+        }).unwrap();
+        Solid::new().build(&mut arena, "menu/item/icon").unwrap();
+        arena.create_ui_node("deep/path").unwrap();
+        for path in ["menu", "menu/item", "menu/item/icon", "deep", "deep/path"] {
+            assert!(arena.borrow_data(path).unwrap().is_some(), "'{}' has no data", path);
+        }
+        assert_eq!(arena.borrow_data("menu").unwrap().unwrap().data, Some(Tag(1)));
+
+        let mut tree: UiTree<NoData, Tag> = arena.clone().into();
+        let root = Rectangle2D::new().with_size((800.0, 600.0)).into();
+        arena.compute(root);
+        tree.compute(root);
+        assert_eq!(arena.validate(), tree.validate());
+
+        let old = arena.clone();
+        arena.move_node("menu/item", "deep/item").unwrap();
+        arena.compute(root);
+        let diff = old.diff_with_data(&arena, 0.01);
+        assert_eq!(diff.changes[0], UiChange::Moved { from: "menu/item".into(), to: "deep/item".into() }, "\n{}", diff);
+
+        let mut history = NodeHistory::new();
+        history.remove_node(&mut arena, "deep/item").unwrap();
+        assert!(arena.borrow_node("deep/item").is_err());
+        history.undo(&mut arena).unwrap();
+        assert_eq!(arena.borrow_data("deep/item/icon").unwrap().map(|data| data.layout), Some(Solid::new().into()));
+    }
+
+    #[test]
+    fn parallel_compute () {
+        use super::{UiNodeComputeTrait, UiNodePresentTrait};
+
+        // One large subtree next to small ones and a large subtree nested in it
+        let mut serial = sample_tree(30);
+        sample_tree(20).node.nodes.into_iter().for_each(|(name, node)| { serial.insert_node(format!("w0/d0/{}", name), node).unwrap(); });
+        let mut sizes = Vec::new();
+        assert_eq!(serial.node.subtree_sizes(&mut sizes), serial.crawl().len() + 1);
+        assert!(sizes[1] >= super::PARALLEL_THRESHOLD && sizes[sizes[1] + 1] < super::PARALLEL_THRESHOLD);
+
+        let mut parallel = serial.clone();
+        let root = Rectangle2D::new().with_size((800.0, 600.0)).into();
+        assert!(!serial.node.compute_all(root, root, 1.0, 16.0));
+        serial.node.present(root, root);
+        assert!(!parallel.node.compute_all_parallel(root, root, 1.0, 16.0, &sizes));
+        parallel.node.present(root, root);
+        assert_eq!(serial, parallel);
+
+        parallel.compute(root);
+        assert_eq!(serial, parallel);
+    }
+
+    #[test]
+    fn diff () {
+        let root = Rectangle2D::new().with_size((800.0, 600.0)).into();
+        let mut old = sample_tree(3);
+        let moved = old.node_id("w0/d1").unwrap();
+        old.compute(root);
+        assert!(old.diff_with_data(&old.clone(), 0.0).is_empty());
+
+        let mut new = old.clone();
+        new.move_node("w0/d1", "w2/d9").unwrap();
+        new.remove_node("w1/d0").unwrap();
+        new.create_ui_node("w1/extra").unwrap();
+        Solid::new().build(&mut new, "w1/extra/s").unwrap();
+        new.borrow_node_mut("w2/d9/s").unwrap().data.as_mut().unwrap().font_size = Some(20.0);
+        new.borrow_node_mut("w2").unwrap().data.as_mut().unwrap().layout = Window::new().size(Abs(Vec2::splat(10.0))).into();
+        new.compute(root);
+
+        let diff = old.diff(&new, 0.01);
+        assert_eq!(diff.changes[0], UiChange::Moved { from: "w0/d1".into(), to: "w2/d9".into() }, "\n{}", diff);
+        assert!(diff.changes.contains(&UiChange::Removed("w1/d0".into())), "\n{}", diff);
+        assert!(diff.changes.contains(&UiChange::Added("w1/extra".into())), "\n{}", diff);
+        assert!(!diff.changes.contains(&UiChange::Added("w1/extra/s".into())), "\n{}", diff);
+        assert_eq!(new.path_by_id(moved).unwrap(), "w2/d9");
+        assert!(diff.changes_at("w2/d9/s").any(|change| matches!(change, UiChange::FontSize { new: Some(_), .. })), "\n{}", diff);
+        assert!(diff.changes_at("w2").any(|change| matches!(change, UiChange::Layout { .. })), "\n{}", diff);
+        assert!(diff.changes_at("w1/d1").any(|change| matches!(change, UiChange::Rectangle { .. })), "\n{}", diff);
+        assert_eq!(diff.changes_at("w0/d0").count(), 0, "\n{}", diff);
+    }
+
+    #[test]
+    fn world_anchor () {
+        let mut tree: UiTree = UiTree::new("Root");
+        Window::new().pos(Abs(Vec2::new(100.0, 50.0))).size(Abs(Vec2::new(200.0, 100.0))).build(&mut tree, "panel").unwrap();
+        Window::new().pos(Abs(Vec2::new(-25.0, -5.0))).size(Prc(Vec2::new(25.0, 10.0))).build(&mut tree, "panel/nameplate").unwrap();
+        let camera = Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0));
+        let projection = Mat4::orthographic_rh(-400.0, 400.0, -300.0, 300.0, 0.1, 100.0);
+        tree.borrow_data_mut("panel/nameplate").unwrap().unwrap().anchor = Some(WorldAnchor::new(Vec3::ZERO, camera, projection));
+
+        // The point is projected into the root, not into the parent
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        let rectangle = tree.borrow_data("panel/nameplate").unwrap().unwrap().rectangle;
+        assert_eq!((rectangle.pos.xy(), rectangle.size), (Vec2::new(375.0, 295.0), Vec2::new(50.0, 10.0)));
+    }
+
+    #[test]
+    fn validate () {
+        let mut tree = sample_tree(2);
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+        assert_eq!(tree.validate(), Vec::new());
+
+        Div::new().build(&mut tree, "root_div").unwrap();
+        Div::new().build(&mut tree, "root_div/inner").unwrap();
+        Solid::new().size(Abs(Vec2::ZERO)).build(&mut tree, "w0/zero").unwrap();
+        tree.create_node("w1/empty").unwrap();
+        tree.borrow_node_mut("w1").unwrap().data.as_mut().unwrap().font_size = Some(0.0);
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+
+        let diagnostics = tree.validate();
+        let found: Vec<(&str, UiDiagnosticKind)> = diagnostics.iter().map(|diagnostic| (diagnostic.path.as_str(), diagnostic.kind)).collect();
+        assert_eq!(found, vec![
+            ("w0/zero", UiDiagnosticKind::ZeroSolidSize),
+            ("w0/zero", UiDiagnosticKind::NonFiniteRectangle),
+            ("w1", UiDiagnosticKind::InvalidFontSize(0.0)),
+            ("w1/empty", UiDiagnosticKind::MissingData),
+            ("root_div", UiDiagnosticKind::ParametricRoot),
+        ]);
+        assert!(!diagnostics[3].is_error());
+        assert_eq!(diagnostics[4].to_string(), "error: 'root_div' div is not inside a laid out node and is never computed");
+    }
+
+    #[test]
+    fn try_compute () {
+        let root = Rectangle2D::new().with_size((800.0, 600.0)).into();
+        let mut tree = sample_tree(2);
+        assert_eq!(tree.try_compute(root), Ok(()));
+        let mut arena: UiArena = tree.clone().into();
+        assert_eq!(arena.try_compute(root), Ok(()));
+
+        Div::new().build(&mut tree, "w0/d0/s/inner").unwrap();
+        Solid::new().size(Abs(Vec2::ZERO)).build(&mut tree, "w1/zero").unwrap();
+        assert_eq!(tree.try_compute(root), Err(UiError::InvalidSize("w1/zero".into())));
+        tree.remove_node("w1/zero").unwrap();
+        assert_eq!(tree.try_compute(root), Ok(()));
+
+        Div::new().build(&mut tree, "div").unwrap();
+        let mut arena: UiArena = tree.clone().into();
+        assert_eq!(tree.try_compute(root), Err(UiError::UnsupportedNesting("div".into())));
+        assert_eq!(arena.try_compute(root), Err(UiError::UnsupportedNesting("div".into())));
+        assert_eq!(tree.remove_node("div").map(|_| ()), Ok(()));
+
+        // Rectangles of the last successful computation are kept
+        let computed = tree.clone();
+        Window::new().size(Prc(Vec2::new(f32::NAN, 10.0))).build(&mut tree, "nan").unwrap();
+        Window::new().build(&mut tree, "nan/inner").unwrap();
+        tree.borrow_data_mut("w0").unwrap().unwrap().layout = Window::new().size(Abs(Vec2::splat(5.0))).into();
+        assert_eq!(tree.try_compute(root), Err(UiError::InvalidSize("nan".into())));
+        assert_eq!(tree.borrow_data("w0/d0").unwrap().unwrap().rectangle, computed.borrow_data("w0/d0").unwrap().unwrap().rectangle);
+        assert_eq!(tree.borrow_data("nan/inner").unwrap().unwrap().rectangle, Rectangle3D::default());
+
+        let mut arena: UiArena = tree.clone().into();
+        assert_eq!(arena.try_compute(root), Err(UiError::InvalidSize("nan".into())));
+        assert_eq!(arena.borrow_data("w0/d0").unwrap().unwrap().rectangle, computed.borrow_data("w0/d0").unwrap().unwrap().rectangle);
+    }
+
+    #[cfg_attr(feature = "bevy", derive(Component))]
+    #[derive(Debug, Default, Clone, PartialEq)]
+    struct Tag(u32);
+
+    #[test]
+    fn ui_data () {
+        let mut tree: UiTree<NoData, Tag> = UiTree::new("Root");
+        tree.create_ui_node("a/b/c").unwrap();
+        Window::new().build(&mut tree, "x/y").unwrap();
+        for path in ["a", "a/b", "a/b/c", "x", "x/y"] {
+            assert!(tree.borrow_data(path).unwrap().is_some(), "'{}' has no data", path);
+            assert!(tree.borrow_node(path).unwrap().get_id().is_some(), "'{}' has no id", path);
+        }
+        assert_eq!(tree.insert_ui_data("a/b", Tag(5)), Ok(None));
+        assert_eq!(tree.borrow_ui_data("a/b"), Ok(Some(&Tag(5))));
+
+        tree.create_node("a/plain").unwrap();
+        assert_eq!(tree.borrow_ui_data("a/plain"), Err(NodeError::NoData("a/plain".into())));
+        assert_eq!(tree.insert_ui_data("a/plain", Tag(1)), Err(NodeError::NoData("a/plain".into())));
+        let id = tree.borrow_or_create_ui_node("a/plain/more").unwrap().get_id().unwrap();
+        assert_eq!(tree.borrow_ui_data("a/plain"), Ok(None));
+        assert_eq!(tree.path_by_id(id), Ok("a/plain/more".to_owned()));
+    }
+
+    #[test]
+    fn extract () {
+        let mut tree: UiTree = UiTree::new("Root");
+        Window::new().size(Abs(Vec2::new(100.0, 50.0))).build(&mut tree, "window").unwrap();
+        Solid::new().build(&mut tree, "window/solid").unwrap();
+
+        let data = tree.borrow_data_mut("window").unwrap().unwrap();
+        assert_eq!(data.get_extract(LayoutProperty::WindowSize), Some(LayoutValue::Vec2(Abs(Vec2::new(100.0, 50.0)).into())));
+        assert_eq!(data.get_extract(LayoutProperty::SolidSize), None);
+        assert_eq!(data.set_extract(LayoutProperty::WindowSize, Align::END.into()), None);
+        assert_eq!(data.set_extract(LayoutProperty::StackGap, LayoutValue::Vec2(Abs(Vec2::splat(4.0)).into())), Some(LayoutValue::Vec2(NodeSize::new())));
+        assert_eq!(data.stack.gap, Abs(Vec2::splat(4.0)).into());
+
+        let data = tree.borrow_data_mut("window/solid").unwrap().unwrap();
+        assert_eq!(data.set_extract(LayoutProperty::SolidAlignX, Align::END.into()), Some(LayoutValue::Align(Align::CENTER)));
+        assert_eq!(data.get_extract(LayoutProperty::SolidAlignX), Some(LayoutValue::Align(Align::END)));
+
+        let mut div = Div::new();
+        div.set_extract(LayoutProperty::DivMargin, LayoutValue::Vec4(Abs(Vec4::splat(2.0)).into()));
+        assert_eq!(div.margin, Abs(Vec4::splat(2.0)).into());
+    }
+}
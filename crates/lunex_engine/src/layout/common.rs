@@ -1,6 +1,3 @@
-#[cfg(feature = "bevy")]
-use bevy::prelude::Component;
-
 use crate::{import::*, Div};
 use crate::{NiceDisplay, NodeSize};
 
@@ -13,6 +10,7 @@ use super::{Window, Solid};
 /// * [`Align::START`]
 /// * [`Align::CENTER`]
 /// * [`Align::END`]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
 pub struct Align (pub f32);
 impl Align {
@@ -23,6 +21,12 @@ impl Align {
     pub const END: Align = Align(1.0);
     pub const RIGHT: Align = Align(1.0);
 }
+impl Align {
+    /// Interpolates the alignment. `t = 0.0` returns `self` and `t = 1.0` returns `other`.
+    pub fn lerp(self, other: Align, t: f32) -> Self {
+        Align(self.0 + (other.0 - self.0) * t)
+    }
+}
 impl NiceDisplay for Align {
     fn to_nicestr(&self) -> String {
         format!("{}", self.0.to_string().bold())
@@ -200,4 +204,63 @@ impl FlexBox {
         self.gap.set_y(gap);
         self
     }
-}
\ No newline at end of file
+}
+
+
+/// Key of a layout property, used by [`crate::Extract`] for generic access to layout fields.
+/// Each key documents the [`LayoutValue`] variant it holds.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LayoutProperty {
+    /// [`Window::pos`] as [`LayoutValue::Vec2`].
+    WindowPos,
+    /// [`Window::size`] as [`LayoutValue::Vec2`].
+    WindowSize,
+    /// [`Solid::size`] as [`LayoutValue::Vec2`].
+    SolidSize,
+    /// [`Solid::align_x`] as [`LayoutValue::Align`].
+    SolidAlignX,
+    /// [`Solid::align_y`] as [`LayoutValue::Align`].
+    SolidAlignY,
+    /// [`Div::padding`] as [`LayoutValue::Vec4`].
+    DivPadding,
+    /// [`Div::margin`] as [`LayoutValue::Vec4`].
+    DivMargin,
+    /// [`FlexBox::gap`] as [`LayoutValue::Vec2`].
+    StackGap,
+}
+
+/// Value of a layout property, see [`LayoutProperty`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LayoutValue {
+    Vec2(NodeSize<Vec2>),
+    Vec4(NodeSize<Vec4>),
+    Align(Align),
+}
+impl LayoutValue {
+    /// Interpolates the value, see [`NodeSize::lerp`]. Returns `None` if the values are of a different kind.
+    pub fn lerp(self, other: LayoutValue, t: f32) -> Option<Self> {
+        match (self, other) {
+            (LayoutValue::Vec2(a), LayoutValue::Vec2(b)) => Some(LayoutValue::Vec2(a.lerp(b, t))),
+            (LayoutValue::Vec4(a), LayoutValue::Vec4(b)) => Some(LayoutValue::Vec4(a.lerp(b, t))),
+            (LayoutValue::Align(a), LayoutValue::Align(b)) => Some(LayoutValue::Align(a.lerp(b, t))),
+            _ => None,
+        }
+    }
+}
+impl From<NodeSize<Vec2>> for LayoutValue {
+    fn from(value: NodeSize<Vec2>) -> Self {
+        LayoutValue::Vec2(value)
+    }
+}
+impl From<NodeSize<Vec4>> for LayoutValue {
+    fn from(value: NodeSize<Vec4>) -> Self {
+        LayoutValue::Vec4(value)
+    }
+}
+impl From<Align> for LayoutValue {
+    fn from(value: Align) -> Self {
+        LayoutValue::Align(value)
+    }
+}
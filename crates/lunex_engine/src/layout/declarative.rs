@@ -64,6 +64,14 @@ impl Window {
             size: self.size.evaluate(abs_scale, parent.size, font_size),
         }
     }
+    /// Computes the layout with the position offset from the projected anchor instead of the parent position.
+    /// The anchor is projected into the root rectangle, windows with the point behind the camera collapse to zero size.
+    pub(crate) fn compute_anchored(&self, anchor: &WorldAnchor, root: Rectangle2D, parent: Rectangle2D, abs_scale: f32, font_size: f32) -> Rectangle2D {
+        match anchor.project(root) {
+            Some(point) => self.compute(Rectangle2D { pos: point, size: parent.size }, abs_scale, font_size),
+            None => Rectangle2D { pos: parent.pos, size: Vec2::ZERO },
+        }
+    }
     /// Packs the struct into Layout
     pub fn pack(self) -> Layout {
         self.into()
@@ -81,6 +89,37 @@ impl NiceDisplay for Window {
     }
 }
 
+/// World-space point projected through a camera. Set as [`crate::NodeData::anchor`] of [`Window`] nodes following 3D targets, like nameplates.
+/// The position of the window becomes an offset from the projected point, its size is still relative to the parent.
+/// ## 📌 Note
+/// * The point is projected into the root rectangle of the tree, which is expected to cover the whole camera viewport
+/// * Windows with the point behind the camera collapse to zero size
+/// * Anchors of nodes with other layouts are ignored
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct WorldAnchor {
+    /// Point in the world space.
+    pub point: Vec3,
+    /// Matrix transforming world space into clip space of the camera (projection * view).
+    pub clip_from_world: Mat4,
+}
+impl WorldAnchor {
+    /// Creates new anchor from the world point, the world transform of the camera and its projection matrix.
+    pub fn new(point: impl Into<Vec3>, camera_transform: Mat4, projection: Mat4) -> Self {
+        WorldAnchor {
+            point: point.into(),
+            clip_from_world: projection * camera_transform.inverse(),
+        }
+    }
+    /// Projects the point into the viewport rectangle. Returns `None` if the point is behind the camera.
+    /// Points outside of the view are not clipped.
+    pub fn project(&self, viewport: Rectangle2D) -> Option<Vec2> {
+        let clip = self.clip_from_world * self.point.extend(1.0);
+        if clip.w <= 0.0 || !clip.is_finite() { return None }
+        let ndc = clip.truncate() / clip.w;
+        Some(viewport.pos + Vec2::new((ndc.x + 1.0) / 2.0, (1.0 - ndc.y) / 2.0) * viewport.size)
+    }
+}
+
 /// A layout type that tries to fit inside a parent node.
 /// Is not included in the ui flow.
 #[derive(Debug, Default, Clone, Copy, PartialEq)]
@@ -176,3 +215,34 @@ impl NiceDisplay for Solid {
         format!("{}", t.black())
     }
 }
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::{Window, WorldAnchor};
+    use crate::import::*;
+    use crate::{Abs, Prc, Rectangle2D};
+
+    #[test]
+    fn world_anchor () {
+        let camera = Mat4::from_translation(Vec3::new(0.0, 0.0, 10.0));
+        let projection = Mat4::perspective_rh(std::f32::consts::FRAC_PI_2, 2.0, 0.1, 100.0);
+        let viewport = Rectangle2D::new().with_pos((-400.0, -200.0)).with_size((800.0, 400.0));
+
+        assert_eq!(WorldAnchor::new(Vec3::ZERO, camera, projection).project(viewport), Some(Vec2::ZERO));
+        let corner = WorldAnchor::new(Vec3::new(-20.0, 10.0, 0.0), camera, projection).project(viewport).unwrap();
+        assert!(corner.distance(Vec2::new(-400.0, -200.0)) < 0.01);
+        assert_eq!(WorldAnchor::new(Vec3::new(0.0, 0.0, 20.0), camera, projection).project(viewport), None);
+
+        // Window is offset from the point projected into the root, even if its parent is smaller
+        let parent = Rectangle2D::new().with_pos((100.0, 50.0)).with_size((200.0, 100.0));
+        let window = Window::new().pos(Abs(Vec2::new(-25.0, -5.0))).size(Prc(Vec2::new(25.0, 10.0)));
+        let nameplate = window.compute_anchored(&WorldAnchor::new(Vec3::ZERO, camera, projection), viewport, parent, 1.0, 16.0);
+        assert_eq!(nameplate, Rectangle2D::new().with_pos((-25.0, -5.0)).with_size((50.0, 10.0)));
+        let hidden = window.compute_anchored(&WorldAnchor::new(Vec3::new(0.0, 0.0, 20.0), camera, projection), viewport, parent, 1.0, 16.0);
+        assert_eq!(hidden.size, Vec2::ZERO);
+    }
+}
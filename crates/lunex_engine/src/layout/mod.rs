@@ -11,6 +11,8 @@ pub mod prelude {
     pub use super::Div;
     pub use super::Layout;
     pub use super::{Align, Cover};
+    pub use super::WorldAnchor;
+    pub use super::{LayoutProperty, LayoutValue};
     pub use super::{FlexBox, FlexDirection, FlexJustify};
 
     #[allow(non_snake_case)]
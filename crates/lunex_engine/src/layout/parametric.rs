@@ -1,4 +1,3 @@
-use bevy::math::Vec2Swizzles;
 
 use crate::{import::*, Align, NodeSizeEvaluate, Sizing};
 
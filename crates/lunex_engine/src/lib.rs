@@ -1,3 +1,6 @@
+pub mod animation;
+pub use animation::*;
+
 pub mod common;
 pub use common::*;
 
@@ -10,14 +13,24 @@ pub use layout::*;
 pub mod nodes;
 pub use nodes::*;
 
+#[cfg(feature = "bevy")]
+pub mod plugin;
+#[cfg(feature = "bevy")]
+pub use plugin::*;
+
+pub mod testing;
+
 
 // #======================#
 // #=== PRELUDE EXPORT ===#
 
 pub mod prelude {
+    pub use super::animation::prelude::*;
     pub use super::common::prelude::*;
     pub use super::core::prelude::*;
     pub use super::layout::prelude::*;
+    #[cfg(feature = "bevy")]
+    pub use super::plugin::prelude::*;
 }
 
 // #=========================#
@@ -29,8 +42,14 @@ pub(crate) mod import {
     pub(crate) use indexmap::IndexMap as HashMap;
     pub(crate) use colored::Colorize;
 
-    //pub(crate) use glam::{Vec2, Vec3, Vec4};          //Used to de-couple from bevy if needed
-    //pub(crate) use thiserror::Error;
-    pub(crate) use bevy::math::{Vec2, Vec3, Vec4};
-    pub(crate) use bevy::utils::thiserror::Error;
+    pub(crate) use glam::{Vec2, Vec3, Vec4, Mat4};
+    pub(crate) use glam::{Vec2Swizzles, Vec3Swizzles};
+    pub(crate) use thiserror::Error;
+
+    #[cfg(feature = "bevy")]
+    pub(crate) use bevy::ecs::component::Component;
+
+    // Fails to compile if glam and bevy_math drift apart and the math types stop being the same
+    #[cfg(feature = "bevy")]
+    const _: fn(Vec2) -> bevy::math::Vec2 = |vec| vec;
 }
\ No newline at end of file
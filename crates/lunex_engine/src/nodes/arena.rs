@@ -0,0 +1,680 @@
+use crate::import::*;
+use crate::NiceDisplay;
+use super::{Node, NodeTree, NodeError, NodeId};
+use super::{NodeGeneralTrait, NodeCreationTrait, NodeHandleTrait, NodeSubnodesTrait, NodeDataTrait, NodeTopDataTrait, NodeInitTrait, NodeTreeInitTrait, NodeDisplayTrait};
+
+
+// #=================#
+// #=== NODEARENA ===#
+
+/// An alternative storage layout for [`NodeTree`]. All nodes live in a single flat vector
+/// and reference each other with [`NodeId`] handles instead of owning their subnodes.
+/// ## 📏 Structure
+/// The hierarchy is the same as in [`NodeTree`], only the memory layout differs.
+/// * No heap allocation per hierarchy level, freed slots are reused.
+/// * Paths are not cached in every node, they are resolved from parents on demand.
+/// * Any node can be accessed in `O(1)` by its [`NodeId`].
+/// ## ⚙️ Paths
+/// Paths work exactly the same as in [`NodeTree`]. All node traits are implemented, with [`ArenaNode`]
+/// taking the place of [`Node`] as the borrowed subnode type. The [`NodeId`] handles are the slots themselves.
+/// ## 🔄 Conversion
+/// Use [`From`] to convert between [`NodeTree`] and [`NodeArena`]. Subtrees are inserted
+/// and removed as regular [`Node`] values, so they can be moved between the two layouts.
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeArena<D, T> {
+    /// ## Top-level data
+    /// This top-level data is meant to be shared for every node. Example usage is storing `theme` and other surface data.
+    pub data: Option<D>,
+
+    /// All slots, occupied or free. The root node is always at index `0`.
+    entries: Vec<ArenaEntry<T>>,
+    /// Indices of free slots ready to be reused.
+    free: Vec<u32>,
+}
+#[derive(Debug, Clone, PartialEq)]
+struct ArenaEntry<T> {
+    generation: u32,
+    node: Option<ArenaNode<T>>,
+}
+
+/// A struct representing organized data in [`NodeArena`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArenaNode<T> {
+    /// ## Name
+    /// Name of the node. `Cached` & `Read-only`.
+    name: String,
+    /// ## Parent
+    /// Id of the parent node. `None` for the root. `Cached` & `Read-only`.
+    parent: Option<NodeId>,
+    /// ## Depth
+    /// Depth within the hierarchy. `Cached` & `Read-only`.
+    depth: f32,
+
+    /// ## Data
+    /// Optional data this node can have. Example usage is storing `node layout` and other specific data.
+    pub data: Option<T>,
+    /// ## Nodes
+    /// Ids of all subnodes this node contains, in order. `Read-only`.
+    nodes: HashMap<String, NodeId>,
+}
+impl <T> ArenaNode<T> {
+    /// Returns name of the node.
+    pub fn get_name(&self) -> &String {
+        &self.name
+    }
+    /// Returns id of the parent node. `None` for the root.
+    pub fn get_parent(&self) -> Option<NodeId> {
+        self.parent
+    }
+    /// Returns depth within the hierarchy.
+    pub fn get_depth(&self) -> f32 {
+        self.depth
+    }
+    /// Returns id of the subnode with the given name.
+    pub fn get_subnode(&self, name: impl Borrow<str>) -> Option<NodeId> {
+        self.nodes.get(name.borrow()).copied()
+    }
+    /// Returns names and ids of all subnodes in order.
+    pub fn get_subnodes(&self) -> &HashMap<String, NodeId> {
+        &self.nodes
+    }
+}
+impl <T> NodeSubnodesTrait for ArenaNode<T> {
+    fn subnode_len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn subnode_index(&self, name: &str) -> Option<usize> {
+        self.nodes.get_index_of(name)
+    }
+
+    fn subnode_name(&self, index: usize) -> Option<&String> {
+        self.nodes.get_index(index).map(|(name, _)| name)
+    }
+}
+
+impl <D, T> NodeArena<D, T> {
+    /// Returns id of the root node.
+    pub fn root(&self) -> NodeId {
+        NodeId { index: 0, generation: self.entries[0].generation }
+    }
+    /// Returns the number of nodes including the root.
+    pub fn len(&self) -> usize {
+        self.entries.len() - self.free.len()
+    }
+    /// Returns `true` if the root node has no subnodes.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 1
+    }
+    /// Borrows node by its id.
+    pub fn get(&self, id: NodeId) -> Result<&ArenaNode<T>, NodeError> {
+        match self.entries.get(id.index as usize) {
+            Some(ArenaEntry { generation, node: Some(node) }) if *generation == id.generation => Ok(node),
+            _ => Err(NodeError::InvalidId(id)),
+        }
+    }
+    /// Borrows node by its id as mut.
+    pub fn get_mut(&mut self, id: NodeId) -> Result<&mut ArenaNode<T>, NodeError> {
+        match self.entries.get_mut(id.index as usize) {
+            Some(ArenaEntry { generation, node: Some(node) }) if *generation == id.generation => Ok(node),
+            _ => Err(NodeError::InvalidId(id)),
+        }
+    }
+    /// ## 🚸 Recursive
+    /// Returns id of the node at the given path.
+    pub fn locate(&self, path: impl Borrow<str>) -> Result<NodeId, NodeError> {
+        let mut id = self.root();
+        for name in path.borrow().split('/') {
+            if name.is_empty() { return Err(NodeError::InvalidPath(path.borrow().to_owned())) }
+            if name == "." { continue }
+            id = match self.get(id)?.nodes.get(name) {
+                Some(subnode) => *subnode,
+                None => return Err(NodeError::NoNode(name.to_owned())),
+            };
+        }
+        Ok(id)
+    }
+    /// Resolves the full path of the node by walking its parents.
+    pub fn path_of(&self, id: NodeId) -> Result<String, NodeError> {
+        let mut names = Vec::new();
+        let mut node = self.get(id)?;
+        while let Some(parent) = node.parent {
+            names.push(node.name.as_str());
+            node = self.get(parent)?;
+        }
+        names.reverse();
+        Ok(names.join("/"))
+    }
+    /// Recursively returns ids of all subnodes of the root in depth-first order.
+    pub fn crawl_ids(&self) -> Vec<NodeId> {
+        let mut vector = Vec::with_capacity(self.len());
+        let mut stack = vec![self.root()];
+        while let Some(id) = stack.pop() {
+            let Ok(node) = self.get(id) else { continue };
+            if id != self.root() { vector.push(id) }
+            stack.extend(node.nodes.values().rev());
+        }
+        vector
+    }
+
+    /// Splits the path into the id of the parent node and the name.
+    fn locate_parent<'a>(&self, path: &'a str) -> Result<(NodeId, &'a str), NodeError> {
+        match path.rsplit_once('/') {
+            None => Ok((self.root(), path)),
+            Some((rempath, name)) => Ok((self.locate(rempath)?, name)),
+        }
+    }
+    /// Converts the node into arena entries and links them under the parent.
+    fn attach(&mut self, parent: NodeId, name: &str, node: Node<T>) -> Result<NodeId, NodeError> {
+        let nodes = &self.get(parent)?.nodes;
+        let name = if name.is_empty() {
+            // Generate the name the same way NodeTree does
+            let mut generated_name = format!(".||#:{}", nodes.len());
+            let mut i = 0;
+            while nodes.contains_key(&generated_name) {
+                generated_name = format!(".||#:{}", nodes.len()+i);
+                i += 1;
+                if i > 100 { return Err(NodeError::InvalidPath("Failed to generate name, max threshold reached!".to_owned())); }
+            }
+            generated_name
+        } else {
+            if name == "." { return Err(NodeError::NameInUse("The special symbol '.' is used to refer to 'self' and is not available for use".to_owned())) }
+            if nodes.contains_key(name) { return Err(NodeError::NameInUse(name.to_owned())) }
+            name.to_owned()
+        };
+        self.cascade_attach(parent, name, node)
+    }
+    fn cascade_attach(&mut self, parent: NodeId, name: String, node: Node<T>) -> Result<NodeId, NodeError> {
+        let depth = self.get(parent)?.depth + 1.0;
+        let id = self.allocate(ArenaNode {
+            name: name.to_owned(),
+            parent: Some(parent),
+            depth,
+            data: node.data,
+            nodes: HashMap::with_capacity(node.nodes.len()),
+        });
+        self.get_mut(parent)?.nodes.insert(name, id);
+        for (subname, subnode) in node.nodes {
+            self.cascade_attach(id, subname, subnode)?;
+        }
+        Ok(id)
+    }
+    /// Unlinks the node from its parent, frees its slots and converts it back into [`Node`].
+    fn detach(&mut self, id: NodeId) -> Result<Node<T>, NodeError> {
+        let node = self.get(id)?;
+        if let Some(parent) = node.parent {
+            let name = node.name.to_owned();
+            self.get_mut(parent)?.nodes.shift_remove(&name);
+        }
+        self.cascade_detach(id)
+    }
+    fn cascade_detach(&mut self, id: NodeId) -> Result<Node<T>, NodeError> {
+        self.get(id)?;
+        let entry = &mut self.entries[id.index as usize];
+        let Some(arena_node) = entry.node.take() else { return Err(NodeError::InvalidId(id)) };
+        entry.generation += 1;
+        self.free.push(id.index);
+
+        let mut node = Node::new();
+        node.data = arena_node.data;
+        for (name, subnode) in arena_node.nodes {
+            let subnode = self.cascade_detach(subnode)?;
+            node.add_node(name, subnode)?;
+        }
+        Ok(node)
+    }
+    fn allocate(&mut self, node: ArenaNode<T>) -> NodeId {
+        match self.free.pop() {
+            Some(index) => {
+                let entry = &mut self.entries[index as usize];
+                entry.node = Some(node);
+                NodeId { index, generation: entry.generation }
+            },
+            None => {
+                self.entries.push(ArenaEntry { generation: 0, node: Some(node) });
+                NodeId { index: self.entries.len() as u32 - 1, generation: 0 }
+            },
+        }
+    }
+    fn refresh_depth(&mut self, id: NodeId, depth: f32) -> Result<(), NodeError> {
+        self.get_mut(id)?.depth = depth;
+        for index in 0..self.get(id)?.nodes.len() {
+            let subnode = self.get(id)?.nodes[index];
+            self.refresh_depth(subnode, depth + 1.0)?;
+        }
+        Ok(())
+    }
+    fn cascade_tree(&self, id: NodeId, mut string: String, level: u32, param: &str, display: &impl Fn(&ArenaNode<T>) -> String) -> String {
+        let Ok(node) = self.get(id) else { return string };
+        string += &display(node);
+        for (name, subnode) in &node.nodes {
+            if !param.contains("show-hidden") && name.starts_with('.') {continue;}
+            let mut text = String::from("\n  ");
+            for _ in 0..level { text += "|    " }
+            text += "|-> ";
+            string = format!("{}{}{}", string, text.black(), name.bold().yellow());
+            string = self.cascade_tree(*subnode, string, level + 1, param, display);
+        }
+        string
+    }
+    /// Returns the root node.
+    fn root_node(&self) -> &ArenaNode<T> {
+        // Root is never detached
+        self.entries[0].node.as_ref().unwrap_or_else(|| unreachable!())
+    }
+    /// Returns the root node as mut.
+    fn root_node_mut(&mut self) -> &mut ArenaNode<T> {
+        self.entries[0].node.as_mut().unwrap_or_else(|| unreachable!())
+    }
+}
+impl <D, T> NodeGeneralTrait<T> for NodeArena<D, T> {
+    type Handle = NodeId;
+    type Subnode = ArenaNode<T>;
+
+    fn add_node(&mut self, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<NodeId, NodeError> {
+        let root = self.root();
+        self.attach(root, name.borrow(), node.into())
+    }
+
+    fn insert_node(&mut self, path: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<NodeId, NodeError> {
+        let (parent, name) = self.locate_parent(path.borrow())?;
+        self.attach(parent, name, node.into())
+    }
+
+    fn take_node(&mut self, name: impl Borrow<str>) -> Result<Node<T>, NodeError> {
+        let Some(id) = self.root_node().nodes.get(name.borrow()).copied() else { return Err(NodeError::NoNode(name.borrow().to_owned())) };
+        self.detach(id)
+    }
+
+    fn remove_node(&mut self, path: impl Borrow<str>) -> Result<Node<T>, NodeError> {
+        let id = self.locate(path.borrow())?;
+        if id == self.root() { return Err(NodeError::InvalidPath(path.borrow().to_owned())) }
+        self.detach(id)
+    }
+
+    fn obtain_node(&self, name: impl Borrow<str>) -> Result<&ArenaNode<T>, NodeError> {
+        if name.borrow().contains('/') { return Err(NodeError::InvalidPath(name.borrow().to_owned())) }
+        self.borrow_node(name)
+    }
+
+    fn obtain_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut ArenaNode<T>, NodeError> {
+        if name.borrow().contains('/') { return Err(NodeError::InvalidPath(name.borrow().to_owned())) }
+        self.borrow_node_mut(name)
+    }
+
+    fn borrow_node(&self, path: impl Borrow<str>) -> Result<&ArenaNode<T>, NodeError> {
+        self.get(self.locate(path)?)
+    }
+
+    fn borrow_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut ArenaNode<T>, NodeError> {
+        let id = self.locate(path)?;
+        self.get_mut(id)
+    }
+
+    /// Works the same as [`NodeTree`], but no data is moved in memory.
+    fn move_node(&mut self, from: impl Borrow<str>, to: impl Borrow<str>) -> Result<String, NodeError> {
+        let (from, to) = (from.borrow(), to.borrow());
+        if to == from || to.starts_with(&format!("{}/", from)) { return Err(NodeError::InvalidPath(to.to_owned())) }
+        let (rempath, name) = to.rsplit_once('/').unwrap_or((".", to));
+        if name.is_empty() { return Err(NodeError::InvalidPath(to.to_owned())) }
+        if name == "." { return Err(NodeError::NameInUse("The special symbol '.' is used to refer to 'self' and is not available for use".to_owned())) }
+        let parent = self.locate(rempath)?;
+        if self.get(parent)?.nodes.contains_key(name) { return Err(NodeError::NameInUse(to.to_owned())) }
+        let id = self.locate(from)?;
+        let Some(old_parent) = self.get(id)?.parent else { return Err(NodeError::InvalidPath(from.to_owned())) };
+
+        let old_name = self.get(id)?.name.to_owned();
+        self.get_mut(old_parent)?.nodes.shift_remove(&old_name);
+        self.get_mut(parent)?.nodes.insert(name.to_owned(), id);
+        let depth = self.get(parent)?.depth + 1.0;
+        let node = self.get_mut(id)?;
+        node.name = name.to_owned();
+        node.parent = Some(parent);
+        self.refresh_depth(id, depth)?;
+        Ok(name.to_owned())
+    }
+
+    fn rename_node(&mut self, path: impl Borrow<str>, name: impl Borrow<str>) -> Result<String, NodeError> {
+        let name = name.borrow();
+        if name.is_empty() || name.contains('/') { return Err(NodeError::InvalidPath(name.to_owned())) }
+        if name == "." { return Err(NodeError::NameInUse("The special symbol '.' is used to refer to 'self' and is not available for use".to_owned())) }
+        let id = self.locate(path.borrow())?;
+        let Some(parent) = self.get(id)?.parent else { return Err(NodeError::InvalidPath(path.borrow().to_owned())) };
+        let old_name = self.get(id)?.name.to_owned();
+        if old_name == name { return Ok(name.to_owned()) }
+        if self.get(parent)?.nodes.contains_key(name) { return Err(NodeError::NameInUse(name.to_owned())) }
+
+        let nodes = &mut self.get_mut(parent)?.nodes;
+        let Some((index, _, _)) = nodes.shift_remove_full(&old_name) else { return Err(NodeError::NoNode(old_name)) };
+        nodes.shift_insert(index, name.to_owned(), id);
+        self.get_mut(id)?.name = name.to_owned();
+        Ok(name.to_owned())
+    }
+
+    fn insert_before(&mut self, path: impl Borrow<str>, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<NodeId, NodeError> {
+        let (parent, sibling) = self.locate_parent(path.borrow())?;
+        let Some(index) = self.get(parent)?.nodes.get_index_of(sibling) else { return Err(NodeError::NoNode(sibling.to_owned())) };
+        let id = self.attach(parent, name.borrow(), node.into())?;
+        let nodes = &mut self.get_mut(parent)?.nodes;
+        nodes.move_index(nodes.len() - 1, index);
+        Ok(id)
+    }
+
+    fn insert_after(&mut self, path: impl Borrow<str>, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<NodeId, NodeError> {
+        let (parent, sibling) = self.locate_parent(path.borrow())?;
+        let Some(index) = self.get(parent)?.nodes.get_index_of(sibling) else { return Err(NodeError::NoNode(sibling.to_owned())) };
+        let id = self.attach(parent, name.borrow(), node.into())?;
+        let nodes = &mut self.get_mut(parent)?.nodes;
+        nodes.move_index(nodes.len() - 1, index + 1);
+        Ok(id)
+    }
+
+    fn move_to_index(&mut self, path: impl Borrow<str>, index: usize) -> Result<(), NodeError> {
+        let (parent, name) = self.locate_parent(path.borrow())?;
+        let nodes = &mut self.get_mut(parent)?.nodes;
+        let Some(from) = nodes.get_index_of(name) else { return Err(NodeError::NoNode(name.to_owned())) };
+        if index >= nodes.len() { return Err(NodeError::InvalidIndex(index)) }
+        nodes.move_index(from, index);
+        Ok(())
+    }
+
+    fn swap_nodes(&mut self, path_a: impl Borrow<str>, path_b: impl Borrow<str>) -> Result<(), NodeError> {
+        let (rempath_a, name_a) = path_a.borrow().rsplit_once('/').unwrap_or((".", path_a.borrow()));
+        let (rempath_b, name_b) = path_b.borrow().rsplit_once('/').unwrap_or((".", path_b.borrow()));
+        if rempath_a != rempath_b { return Err(NodeError::InvalidPath(path_b.borrow().to_owned())) }
+        let nodes = &mut self.borrow_node_mut(rempath_a)?.nodes;
+        let Some(a) = nodes.get_index_of(name_a) else { return Err(NodeError::NoNode(name_a.to_owned())) };
+        let Some(b) = nodes.get_index_of(name_b) else { return Err(NodeError::NoNode(name_b.to_owned())) };
+        nodes.swap_indices(a, b);
+        Ok(())
+    }
+
+    fn sort_nodes_by_key<K: Ord>(&mut self, path: impl Borrow<str>, mut key: impl FnMut(&ArenaNode<T>) -> K) -> Result<(), NodeError> {
+        let parent = self.locate(path)?;
+        let mut keyed = Vec::new();
+        for (name, id) in &self.get(parent)?.nodes {
+            keyed.push((key(self.get(*id)?), name.to_owned(), *id));
+        }
+        keyed.sort_by(|a, b| a.0.cmp(&b.0));
+        self.get_mut(parent)?.nodes = keyed.into_iter().map(|(_, name, id)| (name, id)).collect();
+        Ok(())
+    }
+
+    fn merge(&mut self, node: impl Into<Node<T>>) -> Result<(), NodeError> {
+        let node = node.into();
+        for name in node.nodes.keys() {
+            if self.root_node().nodes.contains_key(name) { return Err(NodeError::DuplicateName(name.to_owned())); }
+        }
+        let root = self.root();
+        for (name, node) in node.nodes {
+            self.cascade_attach(root, name, node)?;
+        }
+        Ok(())
+    }
+
+    fn crawl(&self) -> Vec<&ArenaNode<T>> {
+        self.crawl_ids().into_iter().filter_map(|id| self.get(id).ok()).collect()
+    }
+
+    fn tree_node(&self, params: impl Borrow<str>) -> String {
+        format!(
+            "{} {}{}",
+            ">".black(),
+            self.root_node().name.purple().bold().underline(),
+            self.cascade_tree(self.root(), String::new(), 0, params.borrow(), &|_| String::new())
+        )
+    }
+
+    fn get_name(&self) -> &String {
+        &self.root_node().name
+    }
+
+    fn get_path(&self) -> &String {
+        static ROOT_PATH: String = String::new();
+        &ROOT_PATH
+    }
+
+    fn get_depth(&self) -> f32 {
+        self.root_node().depth
+    }
+
+    fn get_id(&self) -> Option<NodeId> {
+        Some(self.root())
+    }
+}
+impl <D, T> NodeCreationTrait<T> for NodeArena<D, T> {
+    fn make_node(&mut self, name: impl Borrow<str>) -> Result<NodeId, NodeError> {
+        self.add_node(name, Node::new())
+    }
+
+    fn create_node(&mut self, path: impl Borrow<str>) -> Result<NodeId, NodeError> {
+        self.insert_node(path, Node::new())
+    }
+
+    fn obtain_or_create_node(&mut self, name: impl Borrow<str>) -> Result<&ArenaNode<T>, NodeError> {
+        self.obtain_or_create_node_mut(name).map(|node| &*node)
+    }
+
+    fn obtain_or_create_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut ArenaNode<T>, NodeError> {
+        if name.borrow().contains('/') { return Err(NodeError::InvalidPath(name.borrow().to_owned())) }
+        self.borrow_or_create_node_mut(name)
+    }
+
+    fn borrow_or_create_node(&mut self, path: impl Borrow<str>) -> Result<&ArenaNode<T>, NodeError> {
+        self.borrow_or_create_node_mut(path).map(|node| &*node)
+    }
+
+    fn borrow_or_create_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut ArenaNode<T>, NodeError> {
+        let mut id = self.root();
+        for name in path.borrow().split('/') {
+            if name.is_empty() { return Err(NodeError::InvalidPath(path.borrow().to_owned())) }
+            if name == "." { continue }
+            id = match self.get(id)?.nodes.get(name) {
+                Some(subnode) => *subnode,
+                None => self.attach(id, name, Node::new())?,
+            };
+        }
+        self.get_mut(id)
+    }
+}
+impl <D, T> NodeHandleTrait<T> for NodeArena<D, T> {
+    fn node_id(&mut self, path: impl Borrow<str>) -> Result<NodeId, NodeError> {
+        let id = self.locate(path.borrow())?;
+        if id == self.root() { return Err(NodeError::InvalidPath(path.borrow().to_owned())) }
+        Ok(id)
+    }
+
+    fn contains_id(&self, id: NodeId) -> bool {
+        self.get(id).is_ok()
+    }
+
+    fn path_by_id(&self, id: NodeId) -> Result<String, NodeError> {
+        self.path_of(id)
+    }
+
+    fn borrow_node_by_id(&self, id: NodeId) -> Result<&ArenaNode<T>, NodeError> {
+        self.get(id)
+    }
+
+    fn borrow_node_by_id_mut(&mut self, id: NodeId) -> Result<&mut ArenaNode<T>, NodeError> {
+        self.get_mut(id)
+    }
+
+    fn borrow_data_by_id(&self, id: NodeId) -> Result<Option<&T>, NodeError> {
+        Ok(self.get(id)?.data.as_ref())
+    }
+
+    fn borrow_data_by_id_mut(&mut self, id: NodeId) -> Result<Option<&mut T>, NodeError> {
+        Ok(self.get_mut(id)?.data.as_mut())
+    }
+
+    fn refresh_ids(&mut self) {
+        // Arena ids are the storage itself, they are never outdated
+    }
+}
+impl <D, T> NodeTreeInitTrait for NodeArena<D, T> {
+    fn new(name: impl Borrow<str>) -> Self {
+        let root = ArenaNode {
+            name: name.borrow().into(),
+            parent: None,
+            depth: 0.0,
+            data: None,
+            nodes: HashMap::new(),
+        };
+        NodeArena { data: None, entries: vec![ArenaEntry { generation: 0, node: Some(root) }], free: Vec::new() }
+    }
+}
+impl <D, T> Default for NodeArena<D, T> {
+    fn default() -> Self {
+        NodeArena::new("")
+    }
+}
+impl <D, T> NodeTopDataTrait<D> for NodeArena<D, T> {
+    fn add_topdata(&mut self, data: D) -> Option<D> {
+        self.data.replace(data)
+    }
+
+    fn take_topdata(&mut self) -> Option<D> {
+        self.data.take()
+    }
+
+    fn obtain_topdata(&self) -> Option<&D> {
+        self.data.as_ref()
+    }
+
+    fn obtain_topdata_mut(&mut self) -> Option<&mut D> {
+        self.data.as_mut()
+    }
+}
+impl <D, T> NodeDataTrait<T> for NodeArena<D, T> {
+    fn add_data(&mut self, data: T) -> Option<T> {
+        self.root_node_mut().data.replace(data)
+    }
+
+    fn insert_data(&mut self, path: impl Borrow<str>, data: T) -> Result<Option<T>, NodeError> {
+        Ok(self.borrow_node_mut(path)?.data.replace(data))
+    }
+
+    fn take_data(&mut self) -> Option<T> {
+        self.root_node_mut().data.take()
+    }
+
+    fn remove_data(&mut self, path: impl Borrow<str>) -> Result<Option<T>, NodeError> {
+        Ok(self.borrow_node_mut(path)?.data.take())
+    }
+
+    fn obtain_data(&self) -> Option<&T> {
+        self.root_node().data.as_ref()
+    }
+
+    fn obtain_data_mut(&mut self) -> Option<&mut T> {
+        self.root_node_mut().data.as_mut()
+    }
+
+    fn borrow_data(&self, path: impl Borrow<str>) -> Result<Option<&T>, NodeError> {
+        Ok(self.borrow_node(path)?.data.as_ref())
+    }
+
+    fn borrow_data_mut(&mut self, path: impl Borrow<str>) -> Result<Option<&mut T>, NodeError> {
+        Ok(self.borrow_node_mut(path)?.data.as_mut())
+    }
+}
+impl <D, T: NiceDisplay> NodeDisplayTrait<T> for NodeArena<D, T> {
+    fn tree(&self, params: impl Borrow<str>) -> String {
+        let root = self.root_node();
+        let params = params.borrow();
+        format!(
+            "{} {}{}",
+            ">".black(),
+            root.name.purple().bold().underline(),
+            self.cascade_tree(self.root(), String::new(), 0, params, &|node| {
+                match &node.data {
+                    Some(data) if !params.contains("no-data") => format!("{}{}", " == ".black(), data.to_nicestr()),
+                    _ => String::new(),
+                }
+            })
+        )
+    }
+}
+impl <D, T> From<NodeTree<D, T>> for NodeArena<D, T> {
+    fn from(tree: NodeTree<D, T>) -> Self {
+        let mut arena = NodeArena::new(tree.get_name().as_str());
+        arena.data = tree.data;
+        let mut node = tree.node;
+        arena.root_node_mut().data = node.data.take();
+        arena.merge(node).unwrap_or_else(|_| unreachable!("Arena is empty"));
+        arena
+    }
+}
+impl <D, T> From<NodeArena<D, T>> for NodeTree<D, T> {
+    fn from(mut arena: NodeArena<D, T>) -> Self {
+        let mut tree = NodeTree::new(arena.root_node().name.as_str());
+        tree.data = arena.data.take();
+        tree.node.data = arena.root_node_mut().data.take();
+        let names: Vec<String> = arena.root_node().nodes.keys().cloned().collect();
+        for name in names {
+            let node = arena.take_node(name.as_str()).expect("Name was just listed");
+            let _ = tree.node.add_node(name, node);
+        }
+        tree
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::{NodeArena, Node, NodeTree, NodeError};
+    use super::super::{NodeGeneralTrait, NodeCreationTrait, NodeHandleTrait, NodeDataTrait, NodeInitTrait, NodeTreeInitTrait};
+
+    #[test]
+    fn arena () {
+        let mut tree: NodeTree<(), u32> = NodeTree::new("Root");
+        for path in ["a", "a/b", "a/b/c", "d"] { tree.create_node(path).unwrap(); }
+        tree.insert_data("a/b/c", 7).unwrap();
+
+        let mut arena: NodeArena<(), u32> = tree.clone().into();
+        assert_eq!(arena.len(), 5);
+        assert_eq!(arena.borrow_data("a/b/c"), Ok(Some(&7)));
+
+        let c = arena.locate("a/b/c").unwrap();
+        assert_eq!(arena.get(c).unwrap().get_depth(), 3.0);
+        arena.move_node("a/b", "d/e").unwrap();
+        assert_eq!(arena.path_of(c), Ok("d/e/c".to_owned()));
+        assert_eq!(arena.get(c).unwrap().get_depth(), 3.0);
+        arena.rename_node("d/e", "f").unwrap();
+        assert_eq!(arena.path_of(c), Ok("d/f/c".to_owned()));
+
+        let removed = arena.remove_node("d/f").unwrap();
+        assert_eq!(removed.borrow_data("c"), Ok(Some(&7)));
+        assert_eq!(arena.get(c), Err(NodeError::InvalidId(c)));
+        arena.insert_node("a/g", removed).unwrap();
+        assert_eq!(arena.borrow_data("a/g/c"), Ok(Some(&7)));
+        assert_ne!(arena.locate("a/g/c"), Ok(c));
+
+        let g = arena.locate("a/g").unwrap();
+        arena.remove_node("a/g").unwrap();
+        assert_eq!(arena.get(g), Err(NodeError::InvalidId(g)));
+        assert_eq!(arena.borrow_node_by_id(g), Err(NodeError::InvalidId(g)));
+
+        // Sibling order edits keep the ids
+        let x = arena.make_node("x").unwrap();
+        arena.insert_before("x", "w", Node::new()).unwrap();
+        arena.insert_after("x", "y", Node::new()).unwrap();
+        let names = |arena: &NodeArena<(), u32>| arena.borrow_node(".").unwrap().get_subnodes().keys().cloned().collect::<Vec<_>>();
+        assert_eq!(names(&arena), ["a", "d", "w", "x", "y"]);
+        arena.swap_nodes("a", "y").unwrap();
+        arena.move_to_index("w", 0).unwrap();
+        assert_eq!(names(&arena), ["w", "y", "d", "x", "a"]);
+        arena.sort_nodes_by_key(".", |node| node.get_name().clone()).unwrap();
+        assert_eq!(names(&arena), ["a", "d", "w", "x", "y"]);
+        assert_eq!(arena.path_by_id(x), Ok("x".to_owned()));
+
+        let back: NodeTree<(), u32> = arena.into();
+        assert_eq!(back.borrow_node("x").unwrap().get_path(), "x");
+        assert_eq!(back.borrow_data("a/b"), Err(NodeError::NoNode("b".into())));
+    }
+}
@@ -0,0 +1,342 @@
+use crate::import::*;
+use super::{Node, NodeError};
+use super::{NodeGeneralTrait, NodeDataTrait, NodeSubnodesTrait};
+
+
+// #===============#
+// #=== COMMAND ===#
+
+/// A single reversible edit of the hierarchy. Applying a command returns another command that reverts it.
+/// Works on anything implementing [`NodeGeneralTrait`] and [`NodeDataTrait`], so [`Node`], [`super::NodeTree`] and [`super::NodeArena`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum NodeCommand<T> {
+    /// Inserts the node at the path (including the name) and places it at the index among its siblings.
+    Insert { path: String, index: usize, node: Node<T> },
+    /// Removes the node at the path.
+    Remove { path: String },
+    /// Moves the node to the new path (including the name) and places it at the index among its siblings.
+    Move { from: String, to: String, index: usize },
+    /// Replaces data of the node at the path. `None` removes the data.
+    Data { path: String, data: Option<T> },
+}
+impl <T> NodeCommand<T> {
+    /// Applies the command and returns the command that reverts it.
+    /// ## ⚠️ Warning
+    /// * Nothing is changed if an error is returned.
+    pub fn apply<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(self, target: &mut S) -> Result<NodeCommand<T>, NodeError> {
+        match self {
+            NodeCommand::Insert { path, index, node } => {
+                let parent = parent_path(&path);
+                if index > target.borrow_node(parent)?.subnode_len() { return Err(NodeError::InvalidIndex(index)) }
+                target.insert_node(path.as_str(), node)?;
+                // Inserted node is always the last sibling
+                let siblings = target.borrow_node(parent)?;
+                let Some(name) = siblings.subnode_name(siblings.subnode_len().wrapping_sub(1)) else { return Err(NodeError::NoNode(path)) };
+                let path = join_path(parent, name);
+                target.move_to_index(path.as_str(), index)?;
+                Ok(NodeCommand::Remove { path })
+            },
+            NodeCommand::Remove { path } => {
+                let index = sibling_index(target, &path)?;
+                let node = target.remove_node(path.as_str())?;
+                Ok(NodeCommand::Insert { path, index, node })
+            },
+            NodeCommand::Move { from, to, index } => {
+                let old_index = sibling_index(target, &from)?;
+                let parent = parent_path(&to);
+                let len = target.borrow_node(parent)?.subnode_len();
+                let limit = if parent == parent_path(&from) { len - 1 } else { len };
+                if index > limit { return Err(NodeError::InvalidIndex(index)) }
+                let to = join_path(parent, &target.move_node(from.as_str(), to.as_str())?);
+                target.move_to_index(to.as_str(), index)?;
+                Ok(NodeCommand::Move { from: to, to: from, index: old_index })
+            },
+            NodeCommand::Data { path, data } => {
+                let old = match data {
+                    Some(data) => target.insert_data(path.as_str(), data)?,
+                    None => target.remove_data(path.as_str())?,
+                };
+                Ok(NodeCommand::Data { path, data: old })
+            },
+        }
+    }
+}
+
+/// Returns the parent part of the path, `"."` for top-level nodes.
+fn parent_path(path: &str) -> &str {
+    path.rsplit_once('/').map(|(parent, _)| parent).unwrap_or(".")
+}
+/// Joins the parent part of the path with the name.
+fn join_path(parent: &str, name: &str) -> String {
+    if parent == "." { name.to_owned() } else { format!("{}/{}", parent, name) }
+}
+/// Returns the index of the node among its siblings.
+fn sibling_index<T>(target: &impl NodeGeneralTrait<T>, path: &str) -> Result<usize, NodeError> {
+    let name = path.rsplit_once('/').map(|(_, name)| name).unwrap_or(path);
+    target.borrow_node(parent_path(path))?.subnode_index(name).ok_or(NodeError::NoNode(path.to_owned()))
+}
+
+
+// #===============#
+// #=== HISTORY ===#
+
+/// Undo/redo history of edits made to a [`super::NodeTree`] or [`Node`].
+/// Edits made through this struct are applied to the target and recorded as reversible [`NodeCommand`]s.
+/// ## 📌 Note
+/// * Edits made directly on the target are not recorded and can make the history invalid
+/// * Commands can be grouped with [`NodeHistory::begin_transaction`] to be undone in a single step
+/// * Reinserted nodes get a new [`super::NodeId`], because ids are never reused
+/// * Data must be [`Clone`], because failed steps are restored from a copy
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeHistory<T> {
+    /// Groups of commands that revert the recorded edits, oldest first.
+    undo: Vec<Vec<NodeCommand<T>>>,
+    /// Groups of commands that reapply the undone edits, oldest first.
+    redo: Vec<Vec<NodeCommand<T>>>,
+    /// Group of commands recorded in the currently open transaction.
+    transaction: Option<Vec<NodeCommand<T>>>,
+}
+impl <T> Default for NodeHistory<T> {
+    fn default() -> Self {
+        NodeHistory { undo: Vec::new(), redo: Vec::new(), transaction: None }
+    }
+}
+impl <T: Clone> NodeHistory<T> {
+    /// Creates new empty history.
+    pub fn new() -> Self {
+        NodeHistory::default()
+    }
+    /// Applies the command to the target and records it.
+    pub fn apply<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S, command: NodeCommand<T>) -> Result<(), NodeError> {
+        let revert = command.apply(target)?;
+        match &mut self.transaction {
+            Some(group) => group.push(revert),
+            None => self.undo.push(vec![revert]),
+        }
+        self.redo.clear();
+        Ok(())
+    }
+    /// ## 🚸 Recursive
+    /// Same as [`NodeGeneralTrait::insert_node`], but recorded. Returns the new path of the node.
+    pub fn insert_node<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S, path: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<String, NodeError> {
+        let path = path.borrow().to_owned();
+        let index = target.borrow_node(parent_path(&path))?.subnode_len();
+        self.apply(target, NodeCommand::Insert { path, index, node: node.into() })?;
+        Ok(self.last_path())
+    }
+    /// ## 🚸 Recursive
+    /// Same as [`NodeGeneralTrait::remove_node`], but recorded. The removed node is kept in the history.
+    pub fn remove_node<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S, path: impl Borrow<str>) -> Result<(), NodeError> {
+        self.apply(target, NodeCommand::Remove { path: path.borrow().to_owned() })
+    }
+    /// ## 🚸 Recursive
+    /// Same as [`NodeGeneralTrait::move_node`], but recorded. Returns the new path of the node.
+    /// The node is placed last among its new siblings.
+    pub fn move_node<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S, from: impl Borrow<str>, to: impl Borrow<str>) -> Result<String, NodeError> {
+        let (from, to) = (from.borrow().to_owned(), to.borrow().to_owned());
+        let len = target.borrow_node(parent_path(&to))?.subnode_len();
+        let index = if parent_path(&from) == parent_path(&to) { len.saturating_sub(1) } else { len };
+        self.apply(target, NodeCommand::Move { from, to, index })?;
+        Ok(self.last_path())
+    }
+    /// ## 🚸 Recursive
+    /// Same as [`NodeDataTrait::insert_data`], but recorded.
+    pub fn insert_data<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S, path: impl Borrow<str>, data: T) -> Result<(), NodeError> {
+        self.apply(target, NodeCommand::Data { path: path.borrow().to_owned(), data: Some(data) })
+    }
+    /// ## 🚸 Recursive
+    /// Same as [`NodeDataTrait::remove_data`], but recorded.
+    pub fn remove_data<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S, path: impl Borrow<str>) -> Result<(), NodeError> {
+        self.apply(target, NodeCommand::Data { path: path.borrow().to_owned(), data: None })
+    }
+    /// ## 🚸 Recursive
+    /// Modifies data of the node with the closure and records the change.
+    /// Use it for changes of a single field, like the layout in [`crate::UiTree`].
+    /// ## ⚠️ Warning
+    /// * Returns error if the node has no data.
+    pub fn modify_data<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S, path: impl Borrow<str>, modify: impl FnOnce(&mut T)) -> Result<(), NodeError> {
+        let path = path.borrow();
+        let Some(mut data) = target.borrow_data(path)?.cloned() else { return Err(NodeError::NoData(path.to_owned())) };
+        modify(&mut data);
+        self.insert_data(target, path, data)
+    }
+
+    /// Starts grouping all following edits into a single undo step.
+    /// ## 📌 Note
+    /// * Does nothing if a transaction is already open, so transactions can be nested
+    pub fn begin_transaction(&mut self) {
+        if self.transaction.is_none() { self.transaction = Some(Vec::new()); }
+    }
+    /// Closes the open transaction and records it as a single undo step. Empty transactions are dropped.
+    pub fn commit_transaction(&mut self) {
+        if let Some(group) = self.transaction.take() {
+            if !group.is_empty() { self.undo.push(group); }
+        }
+    }
+    /// Closes the open transaction and reverts all edits made in it.
+    pub fn rollback_transaction<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S) -> Result<(), NodeError> {
+        match self.transaction.take() {
+            Some(mut group) => run(target, &mut group).map(|_| ()),
+            None => Ok(()),
+        }
+    }
+    /// Runs the closure inside a transaction. Commits the transaction if the closure succeeds
+    /// and rolls it back if it returns an error.
+    pub fn transaction<S: NodeGeneralTrait<T> + NodeDataTrait<T>, R>(&mut self, target: &mut S, edit: impl FnOnce(&mut Self, &mut S) -> Result<R, NodeError>) -> Result<R, NodeError> {
+        if self.transaction.is_some() { return edit(self, target) }
+        self.begin_transaction();
+        match edit(self, target) {
+            Ok(result) => {
+                self.commit_transaction();
+                Ok(result)
+            },
+            Err(error) => {
+                self.rollback_transaction(target)?;
+                Err(error)
+            },
+        }
+    }
+
+    /// Reverts the last recorded step. Returns `false` if there is nothing to undo.
+    /// ## ⚠️ Warning
+    /// * Commits the open transaction first.
+    /// * If reverting fails, the target is restored and the step is kept.
+    pub fn undo<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S) -> Result<bool, NodeError> {
+        self.commit_transaction();
+        let Some(mut group) = self.undo.pop() else { return Ok(false) };
+        match run(target, &mut group) {
+            Ok(reverted) => self.redo.push(reverted),
+            Err(error) => {
+                self.undo.push(group);
+                return Err(error);
+            },
+        }
+        Ok(true)
+    }
+    /// Reapplies the last undone step. Returns `false` if there is nothing to redo.
+    /// ## ⚠️ Warning
+    /// * If reapplying fails, the target is restored and the step is kept.
+    pub fn redo<S: NodeGeneralTrait<T> + NodeDataTrait<T>>(&mut self, target: &mut S) -> Result<bool, NodeError> {
+        self.commit_transaction();
+        let Some(mut group) = self.redo.pop() else { return Ok(false) };
+        match run(target, &mut group) {
+            Ok(reverted) => self.undo.push(reverted),
+            Err(error) => {
+                self.redo.push(group);
+                return Err(error);
+            },
+        }
+        Ok(true)
+    }
+    /// Returns `true` if there is a step to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty() || self.transaction.as_ref().is_some_and(|group| !group.is_empty())
+    }
+    /// Returns `true` if there is a step to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+    /// Forgets all recorded steps. The open transaction is dropped without reverting.
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+        self.transaction = None;
+    }
+
+    /// Returns the path the last recorded command reverts, which is the path of the last edited node.
+    fn last_path(&self) -> String {
+        let last = match &self.transaction {
+            Some(group) => group.last(),
+            None => self.undo.last().and_then(|group| group.last()),
+        };
+        match last {
+            Some(NodeCommand::Remove { path }) | Some(NodeCommand::Move { from: path, .. }) => path.clone(),
+            _ => String::new(),
+        }
+    }
+}
+
+/// Drains the group of commands in reverse order, applies them and returns the commands reverting them.
+/// If any command fails, the already applied ones are reverted and the group is left intact.
+fn run<T: Clone, S: NodeGeneralTrait<T> + NodeDataTrait<T>>(target: &mut S, group: &mut Vec<NodeCommand<T>>) -> Result<Vec<NodeCommand<T>>, NodeError> {
+    let mut reverted = Vec::with_capacity(group.len());
+    while let Some(command) = group.pop() {
+        let backup = command.clone();
+        match command.apply(target) {
+            Ok(revert) => reverted.push(revert),
+            Err(error) => {
+                group.push(backup);
+                if let Ok(applied) = run(target, &mut reverted) { group.extend(applied); }
+                return Err(error);
+            },
+        }
+    }
+    Ok(reverted)
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::{NodeHistory, NodeCommand};
+    use super::super::{Node, NodeTree, NodeError};
+    use super::super::{NodeGeneralTrait, NodeCreationTrait, NodeDataTrait, NodeTreeInitTrait, NodeInitTrait};
+
+    /// Returns the hierarchy without issued ids, because reinserted nodes get new ones.
+    fn unidentified(tree: &NodeTree<(), u32>) -> Node<u32> {
+        let mut node = tree.node.clone();
+        node.clear_ids();
+        node
+    }
+
+    #[test]
+    fn history () {
+        let mut tree: NodeTree<(), u32> = NodeTree::new("Root");
+        for path in ["a", "a/b", "c", "d"] { tree.create_node(path).unwrap(); }
+        tree.insert_data("a/b", 1).unwrap();
+        let original = tree.clone();
+
+        let mut history = NodeHistory::new();
+        history.move_node(&mut tree, "a/b", "d/e").unwrap();
+        history.remove_node(&mut tree, "c").unwrap();
+        history.transaction(&mut tree, |history, tree| {
+            assert_eq!(history.insert_node(tree, "a/f", Node::new())?, "a/f");
+            history.insert_data(tree, "a/f", 2)?;
+            history.modify_data(tree, "d/e", |data| *data += 10)
+        }).unwrap();
+        assert_eq!(tree.borrow_data("d/e"), Ok(Some(&11)));
+        assert_eq!(tree.borrow_data("a/f"), Ok(Some(&2)));
+        let edited = tree.clone();
+
+        // Transaction is a single step
+        assert_eq!(history.undo(&mut tree), Ok(true));
+        assert_eq!(tree.borrow_node("a/f").err(), Some(NodeError::NoNode("f".into())));
+        assert_eq!(tree.borrow_data("d/e"), Ok(Some(&1)));
+
+        // Order of siblings is restored
+        while history.undo(&mut tree).unwrap() {}
+        assert_eq!(unidentified(&tree), unidentified(&original));
+        assert!(!history.can_undo());
+
+        while history.redo(&mut tree).unwrap() {}
+        assert_eq!(unidentified(&tree), unidentified(&edited));
+
+        // Failed transaction is rolled back and not recorded
+        let result = history.transaction(&mut tree, |history, tree| {
+            history.remove_node(tree, "a")?;
+            history.remove_node(tree, "missing")
+        });
+        assert!(result.is_err());
+        assert_eq!(unidentified(&tree), unidentified(&edited));
+        assert_eq!(history.undo(&mut tree), Ok(true));
+        assert_eq!(tree.borrow_data("d/e"), Ok(Some(&1)));
+
+        // Commands work on plain nodes too
+        let mut node: Node<u32> = Node::new();
+        let revert = NodeCommand::Insert { path: "x".into(), index: 0, node: Node::new() }.apply(&mut node).unwrap();
+        assert_eq!(revert, NodeCommand::Remove { path: "x".into() });
+    }
+}
@@ -1,3 +1,9 @@
+mod arena;
+pub use arena::*;
+
+mod history;
+pub use history::*;
+
 mod structs;
 pub use structs::*;
 
@@ -6,6 +12,10 @@ pub use traits::*;
 
 pub mod prelude {
     pub use super::{NodeGeneralTrait, NodeCreationTrait, NodeDataTrait, NodeTopDataTrait, NodeInitTrait, NodeTreeInitTrait, NodeDisplayTrait};
+    pub use super::{NodeIterTrait, NodeVisitor, NodeVisitorMut, NodeVisit, NodeMut};
+    pub use super::{NodeHandleTrait, NodeSubnodesTrait, NodeId};
     pub use super::{Node, NodeTree};
+    pub use super::{NodeArena, ArenaNode};
+    pub use super::{NodeHistory, NodeCommand};
     pub use super::NodeError;
 }
\ No newline at end of file
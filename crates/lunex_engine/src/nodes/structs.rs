@@ -1,7 +1,8 @@
-use bevy::ecs::component::Component;
 use crate::import::*;
 use crate::NiceDisplay;
+use std::collections::VecDeque;
 use super::{NodeGeneralTrait, NodeCreationTrait, NodeDataTrait, NodeTopDataTrait, NodeInitTrait, NodeTreeInitTrait, NodeDisplayTrait};
+use super::{NodeIterTrait, NodeVisitor, NodeVisitorMut, NodeHandleTrait, NodeSubnodesTrait};
 
 
 // #==================#
@@ -27,6 +28,18 @@ pub enum NodeError {
     /// Error that happens when you try to locate a node that doesn't exist.
     #[error("Unable to locate '{0:}' node")]
     NoNode (String),
+
+    /// Error that happens when the index is outside of the subnode range.
+    #[error("Index '{0:}' is out of bounds")]
+    InvalidIndex (usize),
+
+    /// Error that happens when the node id is expired or was issued by another tree.
+    #[error("Node id '{0:?}' is no longer valid")]
+    InvalidId (NodeId),
+
+    /// Error that happens when you try to access data of a node that has none.
+    #[error("Node '{0:}' has no data")]
+    NoData (String),
 }
 
 
@@ -65,7 +78,8 @@ pub enum NodeError {
 /// * Generic `(N)` - Node data schema struct defining what node-specific data can be stored in [`Node`]
 /// ## ⚠️ Warning
 /// Please refrain from manually using `".||#:0"`, `".||#:1"`, `".||#:2"`, ... as names or [`NodeGeneralTrait::add_node`] will return errors.
-#[derive(Component, Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct NodeTree<D, T> {
     /// ## Top-level data
     /// This top-level data is meant to be shared for every node. Example usage is storing `theme` and other surface data.
@@ -74,6 +88,10 @@ pub struct NodeTree<D, T> {
     /// ## Node
     /// The starting root node.
     pub node: Node<T>,
+
+    /// ## Registry
+    /// Lookup table for resolving [`NodeId`] handles.
+    registry: NodeRegistry,
 }
 impl <D, T> NodeTopDataTrait<D> for NodeTree<D, T> {
     fn add_topdata(&mut self, data: D) -> Option<D> {
@@ -103,24 +121,83 @@ impl <D, T> NodeTreeInitTrait for NodeTree<D, T> {
         let mut node = Node::new();
         node.name = name.borrow().into();
         node.path = "".into();
-        NodeTree { data: None, node }
+        NodeTree { data: None, node, registry: NodeRegistry::default() }
+    }
+}
+impl <D, T> NodeTree<D, T> {
+    /// Splits the path into the parent path and the index of the node among its siblings.
+    fn locate<'a>(&self, path: &'a str) -> Option<(&'a str, usize)> {
+        let (rempath, name) = path.rsplit_once('/').unwrap_or((".", path));
+        Some((rempath, self.node.borrow_node(rempath).ok()?.nodes.get_index_of(name)?))
+    }
+    /// Updates cached id chains of subnodes of the node at the path, starting at the sibling index.
+    /// Must be called after every edit that shifts indices of siblings.
+    fn sync_ids(&mut self, path: &str, from: usize) {
+        let mut chain = Vec::new();
+        let mut node = &self.node;
+        for name in path.split('/') {
+            if name == "." { continue }
+            let Some((index, _, subnode)) = node.nodes.get_full(name) else { return };
+            chain.push(index);
+            node = subnode;
+        }
+        self.registry.sync(node, &mut chain, from);
+    }
+    /// Issues id for the node that was just inserted at the parent path under the name.
+    fn issue_id(&mut self, rempath: &str, name: &str) -> Result<NodeId, NodeError> {
+        if rempath == "." { self.node_id(name) } else { self.node_id(format!("{}/{}", rempath, name)) }
+    }
+    /// Issues ids for all nodes in the path that don't have one yet. Used after creating missing nodes in the path.
+    pub(crate) fn issue_path_ids(&mut self, path: &str) -> Result<(), NodeError> {
+        if self.registry.needs_collect() { self.registry.rebuild(&mut self.node) }
+
+        let mut chain = Vec::new();
+        let mut node = &mut self.node;
+        for name in path.split('/') {
+            if name.is_empty() || name == "." { continue }
+            let Some((index, _, subnode)) = node.nodes.get_full_mut(name) else { return Err(NodeError::NoNode(name.to_owned())) };
+            chain.push(index);
+            node = subnode;
+            if !node.id.is_some_and(|id| self.registry.contains(id)) {
+                node.id = Some(self.registry.allocate(chain.clone()));
+            }
+        }
+        Ok(())
     }
 }
 impl <D, T> NodeGeneralTrait<T> for NodeTree<D, T> {
-    fn add_node(&mut self, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<String, NodeError>{
-        self.node.add_node(name, node)
+    type Handle = NodeId;
+    type Subnode = Node<T>;
+
+    fn add_node(&mut self, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<NodeId, NodeError>{
+        let mut node = node.into();
+        node.clear_ids();
+        let name = self.node.add_node(name, node)?;
+        self.node_id(name)
     }
 
-    fn insert_node(&mut self, path: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<String, NodeError>{
-        self.node.insert_node(path, node)
+    fn insert_node(&mut self, path: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<NodeId, NodeError>{
+        let mut node = node.into();
+        node.clear_ids();
+        let name = self.node.insert_node(path.borrow(), node)?;
+        let rempath = path.borrow().rsplit_once('/').map_or(".", |(rempath, _)| rempath);
+        self.issue_id(rempath, &name)
     }
 
     fn take_node(&mut self, name: impl Borrow<str>) -> Result<Node<T>, NodeError> {
-        self.node.take_node(name)
+        let located = self.locate(name.borrow()).filter(|(rempath, _)| *rempath == ".");
+        let mut node = self.node.take_node(name.borrow())?;
+        self.registry.release_node(&mut node);
+        if let Some((_, index)) = located { self.sync_ids(".", index) }
+        Ok(node)
     }
 
     fn remove_node(&mut self, path: impl Borrow<str>) -> Result<Node<T>, NodeError> {
-        self.node.remove_node(path)
+        let located = self.locate(path.borrow());
+        let mut node = self.node.remove_node(path.borrow())?;
+        self.registry.release_node(&mut node);
+        if let Some((rempath, index)) = located { self.sync_ids(rempath, index) }
+        Ok(node)
     }
 
     fn obtain_node(&self, name: impl Borrow<str>) -> Result<&Node<T>, NodeError> {
@@ -139,8 +216,62 @@ impl <D, T> NodeGeneralTrait<T> for NodeTree<D, T> {
         self.node.borrow_node_mut(path)
     }
 
+    fn move_node(&mut self, from: impl Borrow<str>, to: impl Borrow<str>) -> Result<String, NodeError> {
+        let located = self.locate(from.borrow());
+        let name = self.node.move_node(from.borrow(), to.borrow())?;
+        if let Some((rempath, index)) = located { self.sync_ids(rempath, index) }
+        let rempath = to.borrow().rsplit_once('/').map_or(".", |(rempath, _)| rempath);
+        let index = self.node.borrow_node(rempath)?.nodes.len() - 1;
+        self.sync_ids(rempath, index);
+        Ok(name)
+    }
+
+    fn rename_node(&mut self, path: impl Borrow<str>, name: impl Borrow<str>) -> Result<String, NodeError> {
+        self.node.rename_node(path, name)
+    }
+
+    fn insert_before(&mut self, path: impl Borrow<str>, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<NodeId, NodeError> {
+        let mut node = node.into();
+        node.clear_ids();
+        let name = self.node.insert_before(path.borrow(), name, node)?;
+        let (rempath, index) = self.locate(path.borrow()).ok_or(NodeError::NoNode(path.borrow().to_owned()))?;
+        self.sync_ids(rempath, index - 1);
+        self.issue_id(rempath, &name)
+    }
+
+    fn insert_after(&mut self, path: impl Borrow<str>, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<NodeId, NodeError> {
+        let mut node = node.into();
+        node.clear_ids();
+        let name = self.node.insert_after(path.borrow(), name, node)?;
+        let (rempath, index) = self.locate(path.borrow()).ok_or(NodeError::NoNode(path.borrow().to_owned()))?;
+        self.sync_ids(rempath, index + 1);
+        self.issue_id(rempath, &name)
+    }
+
+    fn move_to_index(&mut self, path: impl Borrow<str>, index: usize) -> Result<(), NodeError> {
+        let located = self.locate(path.borrow());
+        self.node.move_to_index(path.borrow(), index)?;
+        if let Some((rempath, from)) = located { self.sync_ids(rempath, from.min(index)) }
+        Ok(())
+    }
+
+    fn swap_nodes(&mut self, path_a: impl Borrow<str>, path_b: impl Borrow<str>) -> Result<(), NodeError> {
+        let located = self.locate(path_a.borrow()).zip(self.locate(path_b.borrow()));
+        self.node.swap_nodes(path_a.borrow(), path_b.borrow())?;
+        if let Some(((rempath, a), (_, b))) = located { self.sync_ids(rempath, a.min(b)) }
+        Ok(())
+    }
+
+    fn sort_nodes_by_key<K: Ord>(&mut self, path: impl Borrow<str>, key: impl FnMut(&Node<T>) -> K) -> Result<(), NodeError> {
+        self.node.sort_nodes_by_key(path.borrow(), key)?;
+        self.sync_ids(path.borrow(), 0);
+        Ok(())
+    }
+
     fn merge(&mut self, node: impl Into<Node<T>>) -> Result<(), NodeError> {
-        self.node.merge(node.into())
+        let mut node = node.into();
+        node.clear_ids();
+        self.node.merge(node)
     }
 
     fn crawl(&self) -> Vec<&Node<T>> {
@@ -162,30 +293,41 @@ impl <D, T> NodeGeneralTrait<T> for NodeTree<D, T> {
     fn get_depth(&self) -> f32 {
         self.node.get_depth()
     }
+
+    fn get_id(&self) -> Option<NodeId> {
+        self.node.get_id()
+    }
 }
 impl <D, T> NodeCreationTrait<T> for NodeTree<D, T> {
-    fn make_node(&mut self, name: impl Borrow<str>) -> Result<String, NodeError>{
-        self.node.make_node(name)
+    fn make_node(&mut self, name: impl Borrow<str>) -> Result<NodeId, NodeError>{
+        let name = self.node.make_node(name)?;
+        self.node_id(name)
     }
 
-    fn create_node(&mut self, path: impl Borrow<str>) -> Result<String, NodeError>{
-        self.node.create_node(path)
+    fn create_node(&mut self, path: impl Borrow<str>) -> Result<NodeId, NodeError>{
+        self.insert_node(path, Node::new())
     }
 
     fn obtain_or_create_node(&mut self, name: impl Borrow<str>) -> Result<&Node<T>, NodeError> {
-        self.node.obtain_or_create_node(name)
+        self.obtain_or_create_node_mut(name).map(|node| &*node)
     }
 
     fn obtain_or_create_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut Node<T>, NodeError> {
-        self.node.obtain_or_create_node_mut(name)
+        let path = self.node.obtain_or_create_node_mut(name)?.get_path().to_owned();
+        if path.is_empty() { return Ok(&mut self.node) }
+        self.issue_path_ids(&path)?;
+        self.node.borrow_node_mut(path)
     }
 
     fn borrow_or_create_node(&mut self, path: impl Borrow<str>) -> Result<&Node<T>, NodeError> {
-        self.node.borrow_or_create_node(path)
+        self.borrow_or_create_node_mut(path).map(|node| &*node)
     }
 
     fn borrow_or_create_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut Node<T>, NodeError> {
-        self.node.borrow_or_create_node_mut(path)
+        let path = self.node.borrow_or_create_node_mut(path)?.get_path().to_owned();
+        if path.is_empty() { return Ok(&mut self.node) }
+        self.issue_path_ids(&path)?;
+        self.node.borrow_node_mut(path)
     }
 }
 impl <D, T> NodeDataTrait<T> for NodeTree<D, T> {
@@ -221,6 +363,96 @@ impl <D, T> NodeDataTrait<T> for NodeTree<D, T> {
         self.node.borrow_data_mut(path)
     }
 }
+impl <D, T> NodeIterTrait<T> for NodeTree<D, T> {
+    fn iter_dfs(&self) -> NodeDfsIter<'_, T> {
+        self.node.iter_dfs()
+    }
+
+    fn iter_dfs_mut(&mut self) -> NodeDfsIterMut<'_, T> {
+        self.node.iter_dfs_mut()
+    }
+
+    fn iter_bfs(&self) -> NodeBfsIter<'_, T> {
+        self.node.iter_bfs()
+    }
+
+    fn iter_bfs_mut(&mut self) -> NodeBfsIterMut<'_, T> {
+        self.node.iter_bfs_mut()
+    }
+
+    fn visit(&self, visitor: &mut impl NodeVisitor<T>) {
+        self.node.visit(visitor)
+    }
+
+    fn visit_mut(&mut self, visitor: &mut impl NodeVisitorMut<T>) {
+        self.node.visit_mut(visitor)
+    }
+}
+impl <D, T> NodeHandleTrait<T> for NodeTree<D, T> {
+    fn node_id(&mut self, path: impl Borrow<str>) -> Result<NodeId, NodeError> {
+        // Expire ids leaked by edits made directly on subnodes
+        if self.registry.needs_collect() { self.registry.rebuild(&mut self.node) }
+
+        let mut chain = Vec::new();
+        let mut node = &mut self.node;
+        for name in path.borrow().split('/') {
+            if name == "." { continue }
+            let Some((index, _, subnode)) = node.nodes.get_full_mut(name) else { return Err(NodeError::NoNode(name.to_owned())) };
+            chain.push(index);
+            node = subnode;
+        }
+        if chain.is_empty() { return Err(NodeError::InvalidPath(path.borrow().to_owned())) }
+        if let Some(id) = node.id {
+            if self.registry.contains(id) { return Ok(id) }
+        }
+        let id = self.registry.allocate(chain);
+        node.id = Some(id);
+        Ok(id)
+    }
+
+    fn contains_id(&self, id: NodeId) -> bool {
+        self.borrow_node_by_id(id).is_ok()
+    }
+
+    fn path_by_id(&self, id: NodeId) -> Result<String, NodeError> {
+        Ok(self.borrow_node_by_id(id)?.get_path().to_owned())
+    }
+
+    fn borrow_node_by_id(&self, id: NodeId) -> Result<&Node<T>, NodeError> {
+        let Some(chain) = self.registry.chain(id) else { return Err(NodeError::InvalidId(id)) };
+        if let Some(node) = self.node.resolve_chain(chain) {
+            if node.id == Some(id) { return Ok(node) }
+        }
+        // Cached chain is outdated by edits made directly on subnodes, fall back to searching the hierarchy
+        self.node.find_id(id).ok_or(NodeError::InvalidId(id))
+    }
+
+    fn borrow_node_by_id_mut(&mut self, id: NodeId) -> Result<&mut Node<T>, NodeError> {
+        let Some(chain) = self.registry.chain(id) else { return Err(NodeError::InvalidId(id)) };
+        let valid = matches!(self.node.resolve_chain(chain), Some(node) if node.id == Some(id));
+        if !valid {
+            // Cached chain is outdated, rebuild the registry from the hierarchy
+            self.registry.rebuild(&mut self.node);
+        }
+        let Some(chain) = self.registry.chain(id) else { return Err(NodeError::InvalidId(id)) };
+        match self.node.resolve_chain_mut(chain) {
+            Some(node) => Ok(node),
+            None => Err(NodeError::InvalidId(id)),
+        }
+    }
+
+    fn borrow_data_by_id(&self, id: NodeId) -> Result<Option<&T>, NodeError> {
+        Ok(self.borrow_node_by_id(id)?.obtain_data())
+    }
+
+    fn borrow_data_by_id_mut(&mut self, id: NodeId) -> Result<Option<&mut T>, NodeError> {
+        Ok(self.borrow_node_by_id_mut(id)?.obtain_data_mut())
+    }
+
+    fn refresh_ids(&mut self) {
+        self.registry.rebuild(&mut self.node);
+    }
+}
 impl <D, T: NiceDisplay> NodeDisplayTrait<T> for NodeTree<D, T> {
     fn tree(&self, params: impl Borrow<str>) -> String {
         self.node.tree(params)
@@ -237,17 +469,21 @@ impl <D, T> Into<Node<T>> for NodeTree<D, T>{
 // #=== NODE ===#
 
 /// A struct representing organized data in [`NodeTree`].
-#[derive(Component, Debug, Default, Clone, PartialEq)]
+#[cfg_attr(feature = "bevy", derive(Component))]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct Node<T> {
     /// ## Name
     /// Name of the node. `Cached` & `Read-only`.
     name: String,
     /// ## Path
-    /// Full path without the name. `Cached` & `Read-only`.
+    /// Full path including the name. `Cached` & `Read-only`.
     path: String,
     /// ## Depth
     /// Depth within the hierarchy. `Cached` & `Read-only`.
     depth: f32,
+    /// ## Id
+    /// Stable handle issued by the owning [`NodeTree`]. `Cached` & `Read-only`.
+    id: Option<NodeId>,
 
     /// ## Data
     /// Optional data this node can have. Example usage is storing `node layout` and other specific data.
@@ -271,6 +507,86 @@ impl <T> Node<T> {
         string
     }
 }
+impl <T> Node<T> {
+    /// Walks the subnodes with the visitor. Returns `false` if the walk was stopped.
+    pub(crate) fn cascade_visit(&self, path: &str, depth: usize, visitor: &mut impl NodeVisitor<T>) -> bool {
+        for (name, node) in &self.nodes {
+            let subpath = if path.is_empty() { name.to_owned() } else { format!("{}/{}", path, name) };
+            match visitor.visit(&subpath, depth + 1, node) {
+                NodeVisit::Continue => if !node.cascade_visit(&subpath, depth + 1, visitor) { return false },
+                NodeVisit::Skip => {},
+                NodeVisit::Stop => return false,
+            }
+        }
+        true
+    }
+    /// Walks the subnodes with the visitor as mut. Returns `false` if the walk was stopped.
+    pub(crate) fn cascade_visit_mut(&mut self, path: &str, depth: usize, visitor: &mut impl NodeVisitorMut<T>) -> bool {
+        for (name, node) in &mut self.nodes {
+            let subpath = if path.is_empty() { name.to_owned() } else { format!("{}/{}", path, name) };
+            match visitor.visit_mut(&subpath, depth + 1, node) {
+                NodeVisit::Continue => if !node.cascade_visit_mut(&subpath, depth + 1, visitor) { return false },
+                NodeVisit::Skip => {},
+                NodeVisit::Stop => return false,
+            }
+        }
+        true
+    }
+    /// Counts nodes of every subtree bottom-up in one pass and pushes the counts in depth-first order.
+    /// The first pushed count belongs to this node and includes it. Returns the count of this node.
+    #[cfg(any(feature = "parallel", test))]
+    pub(crate) fn subtree_sizes(&self, sizes: &mut Vec<usize>) -> usize {
+        let index = sizes.len();
+        sizes.push(1);
+        let size = 1 + self.nodes.values().map(|node| node.subtree_sizes(sizes)).sum::<usize>();
+        sizes[index] = size;
+        size
+    }
+    /// Searches all subnodes depth-first for the node with the id.
+    pub(crate) fn find_id(&self, id: NodeId) -> Option<&Node<T>> {
+        let mut stack = vec![self.nodes.values()];
+        while let Some(iter) = stack.last_mut() {
+            match iter.next() {
+                Some(node) if node.id == Some(id) => return Some(node),
+                Some(node) => stack.push(node.nodes.values()),
+                None => { stack.pop(); },
+            }
+        }
+        None
+    }
+    /// Recursively removes all issued ids from this node and its subnodes.
+    pub(crate) fn clear_ids(&mut self) {
+        self.id = None;
+        for (_, node) in &mut self.nodes {
+            node.clear_ids();
+        }
+    }
+    /// Follows the chain of subnode indices and returns the node at the end.
+    pub(crate) fn resolve_chain(&self, chain: &[usize]) -> Option<&Node<T>> {
+        let mut node = self;
+        for index in chain {
+            node = node.nodes.get_index(*index)?.1;
+        }
+        Some(node)
+    }
+    /// Follows the chain of subnode indices and returns the node at the end as mut.
+    pub(crate) fn resolve_chain_mut(&mut self, chain: &[usize]) -> Option<&mut Node<T>> {
+        let mut node = self;
+        for index in chain {
+            node = node.nodes.get_index_mut(*index)?.1;
+        }
+        Some(node)
+    }
+    /// Recursively updates cached name, path and depth of all subnodes to match this node.
+    pub(crate) fn refresh_cache(&mut self) {
+        for (name, node) in &mut self.nodes {
+            node.name = name.to_owned();
+            node.path = if self.path.is_empty() { name.to_owned() } else { self.path.to_owned() + "/" + name };
+            node.depth = self.depth + 1.0;
+            node.refresh_cache();
+        }
+    }
+}
 impl <T:NiceDisplay> Node<T> {
     /// Generate overview of the inner tree and write the mapped output to the given string with data formatted to a certain level depth
     pub(crate) fn cascade_tree_display(&self, mut string: String, level: u32, param: &str) -> String {
@@ -297,6 +613,7 @@ impl <T> NodeInitTrait for Node<T> {
             name: "".into(),
             path: "".into(),
             depth: 0.0,
+            id: None,
 
             data: None,
             nodes: HashMap::new(),
@@ -304,6 +621,9 @@ impl <T> NodeInitTrait for Node<T> {
     }
 }
 impl <T> NodeGeneralTrait<T> for Node<T> {
+    type Handle = String;
+    type Subnode = Node<T>;
+
     fn add_node(&mut self, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<String, NodeError>{
         let mut node = node.into();
         if !name.borrow().is_empty() {
@@ -312,6 +632,7 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
                 node.name = name.borrow().to_owned();
                 node.path = if self.path.is_empty() { name.borrow().to_owned() } else { self.path.to_owned() + "/" + name.borrow() };
                 node.depth = self.depth + 1.0;
+                node.refresh_cache();
                 self.nodes.insert(name.borrow().to_owned(), node);
                 Ok(name.borrow().to_owned())
             } else {
@@ -328,6 +649,7 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
             node.name = generated_name.to_owned();
             node.path = if self.path.is_empty() { generated_name.to_owned() } else { self.path.to_owned() + "/" + &generated_name };
             node.depth = self.depth + 1.0;
+            node.refresh_cache();
             self.nodes.insert(generated_name.to_owned(), node);
             Ok(generated_name)
         }
@@ -392,6 +714,86 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
         }
     }
 
+    fn move_node(&mut self, from: impl Borrow<str>, to: impl Borrow<str>) -> Result<String, NodeError> {
+        let (from, to) = (from.borrow(), to.borrow());
+        if to == from || to.starts_with(&format!("{}/", from)) { return Err(NodeError::InvalidPath(to.to_owned())) }
+
+        // Validate the destination before detaching anything
+        let (rempath, name) = to.rsplit_once('/').unwrap_or((".", to));
+        if name.is_empty() { return Err(NodeError::InvalidPath(to.to_owned())) }
+        if name == "." { return Err(NodeError::NameInUse("The special symbol '.' is used to refer to 'self' and is not available for use".to_owned())) }
+        if self.borrow_node(rempath)?.nodes.contains_key(name) { return Err(NodeError::NameInUse(to.to_owned())) }
+        self.borrow_node(from)?;
+
+        let node = self.remove_node(from)?;
+        self.insert_node(to, node)
+    }
+
+    fn rename_node(&mut self, path: impl Borrow<str>, name: impl Borrow<str>) -> Result<String, NodeError> {
+        match path.borrow().rsplit_once('/') {
+            Some((rempath, oldname)) => self.borrow_node_mut(rempath)?.rename_node(oldname, name),
+            None => {
+                let (oldname, name) = (path.borrow(), name.borrow());
+                if name.is_empty() || name.contains('/') { return Err(NodeError::InvalidPath(name.to_owned())) }
+                if name == "." { return Err(NodeError::NameInUse("The special symbol '.' is used to refer to 'self' and is not available for use".to_owned())) }
+                if !self.nodes.contains_key(oldname) { return Err(NodeError::NoNode(oldname.to_owned())) }
+                if oldname == name { return Ok(name.to_owned()) }
+                if self.nodes.contains_key(name) { return Err(NodeError::NameInUse(name.to_owned())) }
+
+                let Some((index, _, mut node)) = self.nodes.shift_remove_full(oldname) else { return Err(NodeError::NoNode(oldname.to_owned())) };
+                node.name = name.to_owned();
+                node.path = if self.path.is_empty() { name.to_owned() } else { self.path.to_owned() + "/" + name };
+                node.depth = self.depth + 1.0;
+                node.refresh_cache();
+                self.nodes.shift_insert(index, name.to_owned(), node);
+                Ok(name.to_owned())
+            },
+        }
+    }
+
+    fn insert_before(&mut self, path: impl Borrow<str>, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<String, NodeError> {
+        let (rempath, sibling) = path.borrow().rsplit_once('/').unwrap_or((".", path.borrow()));
+        let parent = self.borrow_node_mut(rempath)?;
+        let Some(index) = parent.nodes.get_index_of(sibling) else { return Err(NodeError::NoNode(sibling.to_owned())) };
+        let name = parent.add_node(name, node)?;
+        parent.nodes.move_index(parent.nodes.len() - 1, index);
+        Ok(name)
+    }
+
+    fn insert_after(&mut self, path: impl Borrow<str>, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<String, NodeError> {
+        let (rempath, sibling) = path.borrow().rsplit_once('/').unwrap_or((".", path.borrow()));
+        let parent = self.borrow_node_mut(rempath)?;
+        let Some(index) = parent.nodes.get_index_of(sibling) else { return Err(NodeError::NoNode(sibling.to_owned())) };
+        let name = parent.add_node(name, node)?;
+        parent.nodes.move_index(parent.nodes.len() - 1, index + 1);
+        Ok(name)
+    }
+
+    fn move_to_index(&mut self, path: impl Borrow<str>, index: usize) -> Result<(), NodeError> {
+        let (rempath, name) = path.borrow().rsplit_once('/').unwrap_or((".", path.borrow()));
+        let parent = self.borrow_node_mut(rempath)?;
+        let Some(from) = parent.nodes.get_index_of(name) else { return Err(NodeError::NoNode(name.to_owned())) };
+        if index >= parent.nodes.len() { return Err(NodeError::InvalidIndex(index)) }
+        parent.nodes.move_index(from, index);
+        Ok(())
+    }
+
+    fn swap_nodes(&mut self, path_a: impl Borrow<str>, path_b: impl Borrow<str>) -> Result<(), NodeError> {
+        let (rempath_a, name_a) = path_a.borrow().rsplit_once('/').unwrap_or((".", path_a.borrow()));
+        let (rempath_b, name_b) = path_b.borrow().rsplit_once('/').unwrap_or((".", path_b.borrow()));
+        if rempath_a != rempath_b { return Err(NodeError::InvalidPath(path_b.borrow().to_owned())) }
+        let parent = self.borrow_node_mut(rempath_a)?;
+        let Some(a) = parent.nodes.get_index_of(name_a) else { return Err(NodeError::NoNode(name_a.to_owned())) };
+        let Some(b) = parent.nodes.get_index_of(name_b) else { return Err(NodeError::NoNode(name_b.to_owned())) };
+        parent.nodes.swap_indices(a, b);
+        Ok(())
+    }
+
+    fn sort_nodes_by_key<K: Ord>(&mut self, path: impl Borrow<str>, mut key: impl FnMut(&Node<T>) -> K) -> Result<(), NodeError> {
+        self.borrow_node_mut(path)?.nodes.sort_by(|_, a, _, b| key(a).cmp(&key(b)));
+        Ok(())
+    }
+
     fn merge(&mut self, node: impl Into<Node<T>>) -> Result<(), NodeError> {
         let node = node.into();
         //if let Some(_) = node.data { return Err(NodeError::DataConflict); }
@@ -406,10 +808,15 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
 
     fn crawl(&self) -> Vec<&Node<T>> {
         let mut vector = Vec::new();
-        for (_, node) in &self.nodes{
-            vector.push(node);
-            let mut content = node.crawl();
-            vector.append(&mut content);
+        let mut stack = vec![self.nodes.values()];
+        while let Some(iter) = stack.last_mut() {
+            match iter.next() {
+                Some(node) => {
+                    vector.push(node);
+                    stack.push(node.nodes.values());
+                },
+                None => { stack.pop(); },
+            }
         }
         vector
     }
@@ -435,6 +842,10 @@ impl <T> NodeGeneralTrait<T> for Node<T> {
     fn get_depth(&self) -> f32 {
         self.depth
     }
+
+    fn get_id(&self) -> Option<NodeId> {
+        self.id
+    }
 }
 impl <T> NodeCreationTrait<T> for Node<T> {
     fn make_node(&mut self, name: impl Borrow<str>) -> Result<String, NodeError> {
@@ -528,6 +939,46 @@ impl <T> NodeDataTrait<T> for Node<T> {
         Ok(self.borrow_node_mut(path)?.obtain_data_mut())
     }
 }
+impl <T> NodeSubnodesTrait for Node<T> {
+    fn subnode_len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    fn subnode_index(&self, name: &str) -> Option<usize> {
+        self.nodes.get_index_of(name)
+    }
+
+    fn subnode_name(&self, index: usize) -> Option<&String> {
+        self.nodes.get_index(index).map(|(name, _)| name)
+    }
+}
+impl <T> NodeIterTrait<T> for Node<T> {
+    fn iter_dfs(&self) -> NodeDfsIter<'_, T> {
+        NodeDfsIter { stack: vec![(String::new(), 0, self.nodes.iter())], skippable: false }
+    }
+
+    fn iter_dfs_mut(&mut self) -> NodeDfsIterMut<'_, T> {
+        NodeDfsIterMut { stack: vec![(String::new(), 0, self.nodes.iter_mut())], skippable: false }
+    }
+
+    fn iter_bfs(&self) -> NodeBfsIter<'_, T> {
+        let queue = self.nodes.iter().map(|(name, node)| (name.to_owned(), 1, node)).collect();
+        NodeBfsIter { queue, last_pushed: 0 }
+    }
+
+    fn iter_bfs_mut(&mut self) -> NodeBfsIterMut<'_, T> {
+        let queue = self.nodes.iter_mut().map(|(name, node)| (name.to_owned(), 1, node)).collect();
+        NodeBfsIterMut { queue, last_pushed: 0 }
+    }
+
+    fn visit(&self, visitor: &mut impl NodeVisitor<T>) {
+        self.cascade_visit("", 0, visitor);
+    }
+
+    fn visit_mut(&mut self, visitor: &mut impl NodeVisitorMut<T>) {
+        self.cascade_visit_mut("", 0, visitor);
+    }
+}
 impl <T:NiceDisplay> NodeDisplayTrait<T> for Node<T> {
     fn tree(&self, params: impl Borrow<str>) -> String {
         let text = String::new();
@@ -539,3 +990,489 @@ impl <T:NiceDisplay> NodeDisplayTrait<T> for Node<T> {
         )
     }
 }
+
+
+// #===============#
+// #=== NODE ID ===#
+
+/// ## Node id
+/// Stable and copyable handle to a node inside [`NodeTree`]. It is a generational index, so ids
+/// of removed nodes are never confused with ids issued later. Ids survive moving, renaming and
+/// reordering of nodes. Obtain them with [`NodeHandleTrait`] methods.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct NodeId {
+    pub(crate) index: u32,
+    pub(crate) generation: u32,
+}
+impl NodeId {
+    /// Returns the slot index of this id.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+    /// Returns the generation of this id. It increases every time the slot is reused.
+    pub fn generation(&self) -> u32 {
+        self.generation
+    }
+}
+
+/// Lookup table mapping [`NodeId`] to the chain of subnode indices leading to the node.
+/// ## 📌 Note
+/// * Ids leaked by edits made directly on subnodes are collected once the table holds
+///   twice as many ids as after the last rebuild, so the table never grows unbounded
+#[derive(Debug, Default, Clone, PartialEq)]
+pub(crate) struct NodeRegistry {
+    slots: Vec<NodeSlot>,
+    free: Vec<u32>,
+    collect_at: usize,
+}
+#[derive(Debug, Clone, PartialEq)]
+struct NodeSlot {
+    generation: u32,
+    chain: Option<Vec<usize>>,
+}
+impl NodeRegistry {
+    /// Issues new id for the node located at the chain.
+    pub(crate) fn allocate(&mut self, chain: Vec<usize>) -> NodeId {
+        match self.free.pop() {
+            Some(index) => {
+                let slot = &mut self.slots[index as usize];
+                slot.chain = Some(chain);
+                NodeId { index, generation: slot.generation }
+            },
+            None => {
+                self.slots.push(NodeSlot { generation: 0, chain: Some(chain) });
+                NodeId { index: self.slots.len() as u32 - 1, generation: 0 }
+            },
+        }
+    }
+    /// Returns `true` if enough ids were issued since the last rebuild that leaked ids should be collected.
+    pub(crate) fn needs_collect(&self) -> bool {
+        self.slots.len() - self.free.len() >= self.collect_at.max(64)
+    }
+    /// Returns `true` if the id is currently issued.
+    pub(crate) fn contains(&self, id: NodeId) -> bool {
+        self.chain(id).is_some()
+    }
+    /// Returns the cached chain for the id. The chain might be outdated.
+    pub(crate) fn chain(&self, id: NodeId) -> Option<&Vec<usize>> {
+        match self.slots.get(id.index as usize) {
+            Some(slot) if slot.generation == id.generation => slot.chain.as_ref(),
+            _ => None,
+        }
+    }
+    /// Expires the id so it can't be resolved anymore.
+    pub(crate) fn release(&mut self, id: NodeId) {
+        if !self.contains(id) { return }
+        let slot = &mut self.slots[id.index as usize];
+        slot.generation += 1;
+        slot.chain = None;
+        self.free.push(id.index);
+    }
+    /// Expires ids of the detached node and all of its subnodes.
+    pub(crate) fn release_node<T>(&mut self, node: &mut Node<T>) {
+        if let Some(id) = node.id.take() { self.release(id) }
+        for (_, subnode) in &mut node.nodes {
+            self.release_node(subnode);
+        }
+    }
+    /// Recomputes all chains from the hierarchy. Ids of nodes that are not found are expired.
+    pub(crate) fn rebuild<T>(&mut self, root: &mut Node<T>) {
+        let mut found = vec![false; self.slots.len()];
+        let mut chain = Vec::new();
+        self.cascade_rebuild(root, &mut chain, &mut found);
+        for (index, found) in found.into_iter().enumerate() {
+            if found { continue }
+            let slot = &self.slots[index];
+            self.release(NodeId { index: index as u32, generation: slot.generation });
+        }
+        self.collect_at = 2 * (self.slots.len() - self.free.len());
+    }
+    /// Updates chains of ids in subnodes of the node located at the chain, starting at the sibling index.
+    pub(crate) fn sync<T>(&mut self, node: &Node<T>, chain: &mut Vec<usize>, from: usize) {
+        for (index, (_, subnode)) in node.nodes.iter().enumerate().skip(from) {
+            chain.push(index);
+            if let Some(id) = subnode.id {
+                match self.slots.get_mut(id.index as usize) {
+                    Some(slot) if slot.generation == id.generation => slot.chain = Some(chain.clone()),
+                    _ => {},
+                }
+            }
+            self.sync(subnode, chain, 0);
+            chain.pop();
+        }
+    }
+    fn cascade_rebuild<T>(&mut self, node: &mut Node<T>, chain: &mut Vec<usize>, found: &mut Vec<bool>) {
+        for (index, (_, subnode)) in node.nodes.iter_mut().enumerate() {
+            chain.push(index);
+            if let Some(id) = subnode.id {
+                if self.contains(id) && !found[id.index as usize] {
+                    self.slots[id.index as usize].chain = Some(chain.clone());
+                    found[id.index as usize] = true;
+                } else {
+                    // Expired or duplicated id
+                    subnode.id = None;
+                }
+            }
+            self.cascade_rebuild(subnode, chain, found);
+            chain.pop();
+        }
+    }
+}
+
+
+// #=================#
+// #=== ITERATORS ===#
+
+/// Returned by [`NodeVisitor`] to control the walk over the hierarchy.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum NodeVisit {
+    /// Continue the walk and descend into subnodes of the visited node.
+    #[default]
+    Continue,
+    /// Continue the walk, but don't descend into subnodes of the visited node.
+    Skip,
+    /// Stop the walk entirely.
+    Stop,
+}
+
+/// Lazy depth-first iterator over subnodes. Created by [`NodeIterTrait::iter_dfs`].
+pub struct NodeDfsIter<'a, T> {
+    stack: Vec<(String, usize, indexmap::map::Iter<'a, String, Node<T>>)>,
+    skippable: bool,
+}
+impl <'a, T> NodeDfsIter<'a, T> {
+    /// Skips subnodes of the node that was returned last.
+    pub fn skip_subtree(&mut self) {
+        if self.skippable { self.stack.pop(); }
+        self.skippable = false;
+    }
+}
+impl <'a, T> Iterator for NodeDfsIter<'a, T> {
+    type Item = (String, usize, &'a Node<T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (path, depth, iter) = self.stack.last_mut()?;
+            match iter.next() {
+                Some((name, node)) => {
+                    let subpath = if path.is_empty() { name.to_owned() } else { format!("{}/{}", path, name) };
+                    let depth = *depth + 1;
+                    self.stack.push((subpath.clone(), depth, node.nodes.iter()));
+                    self.skippable = true;
+                    return Some((subpath, depth, node));
+                },
+                None => {
+                    self.stack.pop();
+                    self.skippable = false;
+                },
+            }
+        }
+    }
+}
+
+/// Mutable view of a subnode yielded by [`NodeDfsIterMut`] and [`NodeBfsIterMut`].
+/// Data can be changed, cached fields are `Read-only` and subnodes are yielded by the iterator itself.
+/// ## 📌 Note
+/// * Use [`NodeIterTrait::visit_mut`] for structural edits that need the whole [`Node`]
+#[derive(Debug)]
+pub struct NodeMut<'a, T> {
+    name: &'a String,
+    path: &'a String,
+    depth: f32,
+    id: Option<NodeId>,
+    /// ## Data
+    /// Optional data of the node.
+    pub data: &'a mut Option<T>,
+}
+impl <'a, T> NodeMut<'a, T> {
+    /// Splits the node into the view and its subnodes.
+    fn split(node: &'a mut Node<T>) -> (Self, &'a mut HashMap<String, Node<T>>) {
+        let Node { name, path, depth, id, data, nodes } = node;
+        (NodeMut { name, path, depth: *depth, id: *id, data }, nodes)
+    }
+    /// Returns name of the node. `Cached` & `Read-only`.
+    pub fn get_name(&self) -> &String {
+        self.name
+    }
+    /// Returns full path of the node. `Cached` & `Read-only`.
+    pub fn get_path(&self) -> &String {
+        self.path
+    }
+    /// Returns depth within the hierarchy. `Cached` & `Read-only`.
+    pub fn get_depth(&self) -> f32 {
+        self.depth
+    }
+    /// Returns id of the node if it was issued one. `Cached` & `Read-only`.
+    pub fn get_id(&self) -> Option<NodeId> {
+        self.id
+    }
+    /// Adds new data to the node and returns the previous data.
+    pub fn add_data(&mut self, data: T) -> Option<T> {
+        self.data.replace(data)
+    }
+    /// Removes data from the node and returns it.
+    pub fn take_data(&mut self) -> Option<T> {
+        self.data.take()
+    }
+    /// Borrows data of the node.
+    pub fn obtain_data(&self) -> Option<&T> {
+        self.data.as_ref()
+    }
+    /// Borrows data of the node as mut.
+    pub fn obtain_data_mut(&mut self) -> Option<&mut T> {
+        self.data.as_mut()
+    }
+}
+
+/// Lazy depth-first iterator over mutable subnodes. Created by [`NodeIterTrait::iter_dfs_mut`].
+pub struct NodeDfsIterMut<'a, T> {
+    stack: Vec<(String, usize, indexmap::map::IterMut<'a, String, Node<T>>)>,
+    skippable: bool,
+}
+impl <'a, T> NodeDfsIterMut<'a, T> {
+    /// Skips subnodes of the node that was returned last.
+    pub fn skip_subtree(&mut self) {
+        if self.skippable { self.stack.pop(); }
+        self.skippable = false;
+    }
+}
+impl <'a, T> Iterator for NodeDfsIterMut<'a, T> {
+    type Item = (String, usize, NodeMut<'a, T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (path, depth, iter) = self.stack.last_mut()?;
+            match iter.next() {
+                Some((name, node)) => {
+                    let subpath = if path.is_empty() { name.to_owned() } else { format!("{}/{}", path, name) };
+                    let depth = *depth + 1;
+                    let (node, nodes) = NodeMut::split(node);
+                    self.stack.push((subpath.clone(), depth, nodes.iter_mut()));
+                    self.skippable = true;
+                    return Some((subpath, depth, node));
+                },
+                None => {
+                    self.stack.pop();
+                    self.skippable = false;
+                },
+            }
+        }
+    }
+}
+
+/// Lazy breadth-first iterator over subnodes. Created by [`NodeIterTrait::iter_bfs`].
+pub struct NodeBfsIter<'a, T> {
+    queue: VecDeque<(String, usize, &'a Node<T>)>,
+    last_pushed: usize,
+}
+impl <'a, T> NodeBfsIter<'a, T> {
+    /// Skips subnodes of the node that was returned last.
+    pub fn skip_subtree(&mut self) {
+        self.queue.truncate(self.queue.len() - self.last_pushed);
+        self.last_pushed = 0;
+    }
+}
+impl <'a, T> Iterator for NodeBfsIter<'a, T> {
+    type Item = (String, usize, &'a Node<T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, depth, node) = self.queue.pop_front()?;
+        for (name, subnode) in &node.nodes {
+            self.queue.push_back((format!("{}/{}", path, name), depth + 1, subnode));
+        }
+        self.last_pushed = node.nodes.len();
+        Some((path, depth, node))
+    }
+}
+
+/// Lazy breadth-first iterator over mutable subnodes. Created by [`NodeIterTrait::iter_bfs_mut`].
+pub struct NodeBfsIterMut<'a, T> {
+    queue: VecDeque<(String, usize, &'a mut Node<T>)>,
+    last_pushed: usize,
+}
+impl <'a, T> NodeBfsIterMut<'a, T> {
+    /// Skips subnodes of the node that was returned last.
+    pub fn skip_subtree(&mut self) {
+        self.queue.truncate(self.queue.len() - self.last_pushed);
+        self.last_pushed = 0;
+    }
+}
+impl <'a, T> Iterator for NodeBfsIterMut<'a, T> {
+    type Item = (String, usize, NodeMut<'a, T>);
+    fn next(&mut self) -> Option<Self::Item> {
+        let (path, depth, node) = self.queue.pop_front()?;
+        let (node, nodes) = NodeMut::split(node);
+        self.last_pushed = nodes.len();
+        for (name, subnode) in nodes {
+            self.queue.push_back((format!("{}/{}", path, name), depth + 1, subnode));
+        }
+        Some((path, depth, node))
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::{NodeTree, NodeError};
+    use super::super::{NodeGeneralTrait, NodeCreationTrait, NodeDataTrait, NodeTreeInitTrait, NodeIterTrait, NodeVisit, NodeHandleTrait};
+
+    #[test]
+    fn move_and_rename () {
+        let mut tree: NodeTree<(), ()> = NodeTree::new("Root");
+        tree.create_node("a").unwrap();
+        tree.create_node("a/b").unwrap();
+        tree.create_node("a/b/c").unwrap();
+        tree.create_node("d").unwrap();
+
+        assert_eq!(tree.move_node("a/b", "d/e"), Ok("e".to_owned()));
+        assert!(tree.borrow_node("a/b").is_err());
+        let c = tree.borrow_node("d/e/c").unwrap();
+        assert_eq!(c.get_path(), "d/e/c");
+        assert_eq!(c.get_depth(), 3.0);
+
+        assert_eq!(tree.move_node("d", "d/e/x"), Err(NodeError::InvalidPath("d/e/x".to_owned())));
+        assert_eq!(tree.move_node("d/e", "a"), Err(NodeError::NameInUse("a".to_owned())));
+        assert!(tree.borrow_node("d/e/c").is_ok());
+
+        tree.create_node("d/f").unwrap();
+        assert_eq!(tree.rename_node("d/e", "f"), Err(NodeError::NameInUse("f".to_owned())));
+        assert_eq!(tree.rename_node("d/e", "g"), Ok("g".to_owned()));
+        assert_eq!(tree.borrow_node("d/g/c").unwrap().get_path(), "d/g/c");
+        assert_eq!(tree.borrow_node("d").unwrap().nodes.keys().collect::<Vec<_>>(), ["g", "f"]);
+    }
+
+    #[test]
+    fn sibling_order () {
+        let mut tree: NodeTree<(), ()> = NodeTree::new("Root");
+        tree.create_node("list").unwrap();
+        tree.create_node("list/b").unwrap();
+        tree.create_node("list/d").unwrap();
+        let order = |tree: &NodeTree<(), ()>| tree.borrow_node("list").unwrap().nodes.keys().cloned().collect::<Vec<_>>();
+
+        tree.insert_before("list/b", "a", NodeTree::<(), ()>::new("")).unwrap();
+        tree.insert_after("list/b", "c", NodeTree::<(), ()>::new("")).unwrap();
+        assert_eq!(order(&tree), ["a", "b", "c", "d"]);
+        assert_eq!(tree.borrow_node("list/c").unwrap().get_path(), "list/c");
+
+        tree.move_to_index("list/d", 0).unwrap();
+        assert_eq!(order(&tree), ["d", "a", "b", "c"]);
+        assert_eq!(tree.move_to_index("list/d", 4), Err(NodeError::InvalidIndex(4)));
+
+        tree.swap_nodes("list/a", "list/c").unwrap();
+        assert_eq!(order(&tree), ["d", "c", "b", "a"]);
+        assert!(tree.swap_nodes("list/a", "list").is_err());
+
+        tree.sort_nodes_by_key("list", |node| node.get_name().clone()).unwrap();
+        assert_eq!(order(&tree), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn traversal () {
+        let mut tree: NodeTree<(), u32> = NodeTree::new("Root");
+        for path in ["a", "a/b", "a/b/c", "d", "d/e"] { tree.create_node(path).unwrap(); }
+
+        let dfs: Vec<_> = tree.iter_dfs().map(|(path, depth, _)| (path, depth)).collect();
+        assert_eq!(dfs, [("a".to_owned(), 1), ("a/b".to_owned(), 2), ("a/b/c".to_owned(), 3), ("d".to_owned(), 1), ("d/e".to_owned(), 2)]);
+
+        let bfs: Vec<_> = tree.iter_bfs().map(|(path, _, _)| path).collect();
+        assert_eq!(bfs, ["a", "d", "a/b", "d/e", "a/b/c"]);
+
+        let mut iter = tree.iter_dfs();
+        let mut pruned = Vec::new();
+        while let Some((path, _, _)) = iter.next() {
+            if path == "a" { iter.skip_subtree() }
+            pruned.push(path);
+        }
+        assert_eq!(pruned, ["a", "d", "d/e"]);
+
+        let mut iter = tree.iter_bfs();
+        let mut pruned = Vec::new();
+        while let Some((path, _, _)) = iter.next() {
+            if path == "d" { iter.skip_subtree() }
+            pruned.push(path);
+        }
+        assert_eq!(pruned, ["a", "d", "a/b", "a/b/c"]);
+
+        for (_, depth, mut node) in tree.iter_dfs_mut() { node.add_data(depth as u32); }
+        assert_eq!(tree.borrow_data("a/b/c"), Ok(Some(&3)));
+        for (path, _, node) in tree.iter_bfs_mut() {
+            assert!(path.ends_with(node.get_name().as_str()));
+            *node.data = node.data.map(|d| d * 10);
+        }
+        assert_eq!(tree.borrow_data("d/e"), Ok(Some(&20)));
+
+        let mut visited = Vec::new();
+        tree.visit(&mut |path: &str, _, _: &super::Node<u32>| {
+            visited.push(path.to_owned());
+            if path == "a/b" { NodeVisit::Skip } else if path == "d" { NodeVisit::Stop } else { NodeVisit::Continue }
+        });
+        assert_eq!(visited, ["a", "a/b", "d"]);
+    }
+
+    #[test]
+    fn handles () {
+        let mut tree: NodeTree<(), u32> = NodeTree::new("Root");
+        let a = tree.create_node("a").unwrap();
+        let b = tree.create_node("a/b").unwrap();
+        let c = tree.make_node("c").unwrap();
+        assert_eq!(tree.node_id("a/b"), Ok(b));
+        assert_eq!(tree.path_by_id(b), Ok("a/b".to_owned()));
+
+        tree.insert_data("a/b", 5).unwrap();
+        *tree.borrow_data_by_id_mut(b).unwrap().unwrap() += 1;
+        assert_eq!(tree.borrow_data("a/b"), Ok(Some(&6)));
+
+        // Ids survive structural edits
+        tree.move_node("a/b", "c/d").unwrap();
+        tree.swap_nodes("a", "c").unwrap();
+        assert_eq!(tree.path_by_id(b), Ok("c/d".to_owned()));
+        assert_eq!(tree.borrow_data_by_id_mut(b), Ok(Some(&mut 6)));
+        assert_eq!(tree.path_by_id(a), Ok("a".to_owned()));
+
+        // Ids are invalidated on removal and never reused
+        tree.remove_node("c").unwrap();
+        assert_eq!(tree.borrow_node_by_id(c), Err(NodeError::InvalidId(c)));
+        assert!(!tree.contains_id(b));
+        let e = tree.create_node("e").unwrap();
+        assert_ne!(e, b);
+        assert_ne!(e, c);
+        assert!(tree.contains_id(e));
+
+        // Node level removal is detected lazily
+        tree.node.take_node("e").unwrap();
+        assert_eq!(tree.borrow_node_by_id_mut(e), Err(NodeError::InvalidId(e)));
+    }
+
+    #[test]
+    fn handles_sync () {
+        let mut tree: NodeTree<(), u32> = NodeTree::new("Root");
+        let ids: Vec<_> = ["a", "a/x", "b", "b/y", "c", "c/z"].iter().map(|path| tree.create_node(*path).unwrap()).collect();
+        let synced = |tree: &NodeTree<(), u32>| ids.iter().all(|id| match tree.registry.chain(*id) {
+            Some(chain) => tree.node.resolve_chain(chain).is_some_and(|node| node.id == Some(*id)),
+            None => !tree.contains_id(*id),
+        });
+
+        // Cached chains follow every structural edit, so lookups never search the hierarchy
+        tree.swap_nodes("a", "c").unwrap();
+        assert!(synced(&tree));
+        tree.move_to_index("b", 0).unwrap();
+        assert!(synced(&tree));
+        tree.sort_nodes_by_key(".", |node| node.get_name().clone()).unwrap();
+        assert!(synced(&tree));
+        tree.move_node("a", "c/a").unwrap();
+        assert!(synced(&tree));
+        tree.insert_before("b", "first", NodeTree::<(), u32>::new("")).unwrap();
+        assert!(synced(&tree));
+        tree.remove_node("first").unwrap();
+        tree.take_node("b").unwrap();
+        assert!(synced(&tree));
+        assert_eq!(tree.path_by_id(ids[1]), Ok("c/a/x".to_owned()));
+
+        // Ids leaked by node level removal are collected when the table grows
+        for i in 0..1000 {
+            tree.create_node(format!("leak{}", i)).unwrap();
+            tree.node.take_node(format!("leak{}", i)).unwrap();
+        }
+        assert!(tree.registry.slots.len() < 200);
+    }
+}
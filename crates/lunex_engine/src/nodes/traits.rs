@@ -1,21 +1,27 @@
 use std::borrow::Borrow;
 #[allow(unused_imports)]
-use super::{NodeTree, Node, NodeError};
+use super::{NodeTree, Node, NodeError, NodeId};
+use super::{NodeDfsIter, NodeDfsIterMut, NodeBfsIter, NodeBfsIterMut, NodeVisit};
 
 // #=========================#
 // #=== TRAIT DECLARATION ===#
 
 /// Trait with all node management implementations.
 pub trait NodeGeneralTrait<T> {
-    /// Adds new subnode to this node and returns the new subnodes' name.
+    /// Handle of a new subnode returned by the insert and create methods.
+    /// [`Node`] returns the new subnodes' name, [`NodeTree`] returns its [`NodeId`].
+    type Handle;
+    /// Type of the nodes stored in the hierarchy. [`Node`] for [`NodeTree`], [`crate::ArenaNode`] for [`crate::NodeArena`].
+    type Subnode: NodeSubnodesTrait;
+    /// Adds new subnode to this node and returns its handle.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::insert_node`] for hierarchy insert `(supports path recursion)`
-    fn add_node(&mut self, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<String, NodeError>;
+    fn add_node(&mut self, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<Self::Handle, NodeError>;
     /// ## 🚸 Recursive
-    /// Inserts new subnode to this node or any other subnode and returns the new subnodes' name.
+    /// Inserts new subnode to this node or any other subnode and returns its handle.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::add_node`] for direct insert on this node `(no recursion)`
-    fn insert_node(&mut self, path: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<String, NodeError>;
+    fn insert_node(&mut self, path: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<Self::Handle, NodeError>;
     /// Removes subnode from this node and returns it.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::take_node`] for hierarchy retrieval `(supports path recursion)`
@@ -28,28 +34,66 @@ pub trait NodeGeneralTrait<T> {
     /// Borrows subnode from this node.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::borrow_node`] for hierarchy retrieval `(supports path recursion)`
-    fn obtain_node(&self, name: impl Borrow<str>) -> Result<&Node<T>, NodeError>;
+    fn obtain_node(&self, name: impl Borrow<str>) -> Result<&Self::Subnode, NodeError>;
     /// Borrows subnode from this node as mut.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::borrow_node_mut`] for hierarchy retrieval `(supports path recursion)`
-    fn obtain_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut Node<T>, NodeError>;
+    fn obtain_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut Self::Subnode, NodeError>;
     /// ## 🚸 Recursive
     /// Borrows subnode from this node or any other subnode.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::obtain_node`] for direct retrieval on this node `(no recursion)`
-    fn borrow_node(&self, path: impl Borrow<str>) -> Result<&Node<T>, NodeError>;
+    fn borrow_node(&self, path: impl Borrow<str>) -> Result<&Self::Subnode, NodeError>;
     /// ## 🚸 Recursive
     /// Borrows subnode from this node or any other subnode as mut.
     /// ## 📌 Note
     /// * Use [`NodeGeneralTrait::obtain_node_mut`] for direct retrieval on this node `(no recursion)`
-    fn borrow_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut Node<T>, NodeError>;
+    fn borrow_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut Self::Subnode, NodeError>;
+    /// ## 🚸 Recursive
+    /// Moves subnode from one path to another and returns the new subnodes' name.
+    /// The destination path includes the new name, so the node can be renamed while moving.
+    /// Cached `name`, `path` and `depth` of the node and all of its subnodes are updated.
+    /// ## ⚠️ Warning
+    /// * Returns error if the destination name is already in use or the destination is inside the moved node.
+    /// * Nothing is changed if an error is returned.
+    fn move_node(&mut self, from: impl Borrow<str>, to: impl Borrow<str>) -> Result<String, NodeError>;
+    /// ## 🚸 Recursive
+    /// Renames subnode at the given path and returns the new name. The position among siblings is retained.
+    /// Cached `name`, `path` and `depth` of the node and all of its subnodes are updated.
+    /// ## ⚠️ Warning
+    /// * Returns error if the new name is already in use by a sibling or contains `'/'`.
+    fn rename_node(&mut self, path: impl Borrow<str>, name: impl Borrow<str>) -> Result<String, NodeError>;
+    /// ## 🚸 Recursive
+    /// Inserts new subnode right before the sibling at the given path and returns its handle.
+    /// ## 📌 Note
+    /// * Use [`NodeGeneralTrait::insert_after`] to insert after the sibling instead
+    fn insert_before(&mut self, path: impl Borrow<str>, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<Self::Handle, NodeError>;
+    /// ## 🚸 Recursive
+    /// Inserts new subnode right after the sibling at the given path and returns its handle.
+    /// ## 📌 Note
+    /// * Use [`NodeGeneralTrait::insert_before`] to insert before the sibling instead
+    fn insert_after(&mut self, path: impl Borrow<str>, name: impl Borrow<str>, node: impl Into<Node<T>>) -> Result<Self::Handle, NodeError>;
+    /// ## 🚸 Recursive
+    /// Moves subnode to the given index among its siblings. Other siblings are shifted.
+    /// ## ⚠️ Warning
+    /// * Returns error if the index is out of bounds.
+    fn move_to_index(&mut self, path: impl Borrow<str>, index: usize) -> Result<(), NodeError>;
+    /// ## 🚸 Recursive
+    /// Swaps the positions of two sibling subnodes.
+    /// ## ⚠️ Warning
+    /// * Returns error if the nodes don't share the same parent.
+    fn swap_nodes(&mut self, path_a: impl Borrow<str>, path_b: impl Borrow<str>) -> Result<(), NodeError>;
+    /// ## 🚸 Recursive
+    /// Sorts subnodes of the node at the given path by the key extracted with the supplied function.
+    /// Use `"."` to sort subnodes of this node. The sort is stable.
+    fn sort_nodes_by_key<K: Ord>(&mut self, path: impl Borrow<str>, key: impl FnMut(&Self::Subnode) -> K) -> Result<(), NodeError>;
     /// Merges subnodes of supplied node or nodetree into this node.
     /// ## ⚠️ Warning
     /// * Any data that supplied node contains will be dropped.
     /// * Returns error if there is a name collision.
     fn merge(&mut self, node: impl Into<Node<T>>) -> Result<(), NodeError>;
     /// Recursively iterates over all subnodes and returns them in a single vector.
-    fn crawl(&self) -> Vec<&Node<T>>;
+    fn crawl(&self) -> Vec<&Self::Subnode>;
     /// Generates overview of the inner structure of subnodes as a printable string.
     /// 
     /// You can supply additional parameters like `show-hidden`.
@@ -60,46 +104,144 @@ pub trait NodeGeneralTrait<T> {
     /// ## ⚠️ Warning
     /// * Not guaranteed to be correct if node is not put inside the hierarchy correctly.
     fn get_name(&self) -> &String;
-    /// Returns depth within the hierarchy. `Cached` & `Read-only`.
+    /// Returns full path of the node. `Cached` & `Read-only`.
     /// ## ⚠️ Warning
     /// * Not guaranteed to be correct if node is not put inside the hierarchy correctly.
     fn get_path(&self) -> &String;
-    /// Returns full path without the name. `Cached` & `Read-only`.
+    /// Returns depth within the hierarchy. `Cached` & `Read-only`.
     /// ## ⚠️ Warning
     /// * Not guaranteed to be correct if node is not put inside the hierarchy correctly.
     fn get_depth(&self) -> f32;
+    /// Returns id of the node if it was issued one. `Cached` & `Read-only`.
+    /// ## 📌 Note
+    /// * Use [`NodeHandleTrait::node_id`] to issue an id for existing node
+    fn get_id(&self) -> Option<NodeId>;
+}
+
+/// Trait with read access to subnodes of a single stored node. Implemented by [`Node`] and [`crate::ArenaNode`],
+/// so code generic over [`NodeGeneralTrait::Subnode`] can inspect nodes of any storage.
+pub trait NodeSubnodesTrait {
+    /// Returns the number of direct subnodes.
+    fn subnode_len(&self) -> usize;
+    /// Returns the index of the direct subnode among its siblings.
+    fn subnode_index(&self, name: &str) -> Option<usize>;
+    /// Returns the name of the direct subnode at the index.
+    fn subnode_name(&self, index: usize) -> Option<&String>;
+}
+
+/// Trait with all node handle implementations. Handles are stable [`NodeId`] ids that
+/// can be resolved without parsing paths. Only [`NodeTree`] can issue them.
+/// Nodes inserted or created through [`NodeTree`] methods are issued ids right away.
+/// ## ⚠️ Warning
+/// * Nodes inserted through [`NodeTree`] methods drop ids they carried from elsewhere.
+/// * Ids of nodes removed through [`NodeTree`] methods are expired immediately.
+/// * Ids of nodes removed directly on subnodes are expired lazily during lookup
+///   or when the lookup table grows to twice the number of live ids.
+pub trait NodeHandleTrait<T>: NodeGeneralTrait<T> {
+    /// ## 🚸 Recursive
+    /// Returns id of an existing node. If the node has no id yet, a new one is issued.
+    fn node_id(&mut self, path: impl Borrow<str>) -> Result<NodeId, NodeError>;
+    /// Returns `true` if the id resolves to a node in this tree.
+    fn contains_id(&self, id: NodeId) -> bool;
+    /// Resolves the id back to the full path of the node.
+    fn path_by_id(&self, id: NodeId) -> Result<String, NodeError>;
+    /// Borrows node by its id.
+    fn borrow_node_by_id(&self, id: NodeId) -> Result<&Self::Subnode, NodeError>;
+    /// Borrows node by its id as mut.
+    fn borrow_node_by_id_mut(&mut self, id: NodeId) -> Result<&mut Self::Subnode, NodeError>;
+    /// Borrows data of a node by its id.
+    fn borrow_data_by_id(&self, id: NodeId) -> Result<Option<&T>, NodeError>;
+    /// Borrows data of a node by its id as mut.
+    fn borrow_data_by_id_mut(&mut self, id: NodeId) -> Result<Option<&mut T>, NodeError>;
+    /// Rebuilds the id lookup table after structural edits made directly on subnodes.
+    /// Lookups also rebuild it lazily when they detect outdated entries.
+    fn refresh_ids(&mut self);
+}
+
+/// Trait with all node traversal implementations.
+pub trait NodeIterTrait<T> {
+    /// Returns lazy depth-first (pre-order) iterator over all subnodes.
+    /// Yields `(path, depth, node)` with path and depth relative to this node.
+    /// ## 📌 Note
+    /// * Use [`NodeDfsIter::skip_subtree`] to not descend into the last returned node
+    fn iter_dfs(&self) -> NodeDfsIter<'_, T>;
+    /// Returns lazy depth-first (pre-order) iterator over all subnodes as mut.
+    /// Yields `(path, depth, node)` with path and depth relative to this node.
+    /// ## 📌 Note
+    /// * Yielded [`crate::NodeMut`] can change data only, use [`NodeIterTrait::visit_mut`] for structural edits
+    fn iter_dfs_mut(&mut self) -> NodeDfsIterMut<'_, T>;
+    /// Returns lazy breadth-first iterator over all subnodes.
+    /// Yields `(path, depth, node)` with path and depth relative to this node.
+    /// ## 📌 Note
+    /// * Use [`NodeBfsIter::skip_subtree`] to not descend into the last returned node
+    fn iter_bfs(&self) -> NodeBfsIter<'_, T>;
+    /// Returns lazy breadth-first iterator over all subnodes as mut.
+    /// Yields `(path, depth, node)` with path and depth relative to this node.
+    /// ## 📌 Note
+    /// * Yielded [`crate::NodeMut`] can change data only, use [`NodeIterTrait::visit_mut`] for structural edits
+    fn iter_bfs_mut(&mut self) -> NodeBfsIterMut<'_, T>;
+    /// Walks all subnodes depth-first and calls the visitor on each of them.
+    /// The visitor decides if the walk should continue, skip the subtree or stop.
+    fn visit(&self, visitor: &mut impl NodeVisitor<T>);
+    /// Walks all subnodes depth-first and calls the visitor on each of them as mut.
+    /// The visitor decides if the walk should continue, skip the subtree or stop.
+    fn visit_mut(&mut self, visitor: &mut impl NodeVisitorMut<T>);
+}
+
+/// Trait for types that can be walked over the hierarchy with [`NodeIterTrait::visit`].
+/// It is implemented for all closures with the matching signature.
+pub trait NodeVisitor<T> {
+    /// Called on every visited node with path and depth relative to the starting node.
+    fn visit(&mut self, path: &str, depth: usize, node: &Node<T>) -> NodeVisit;
+}
+impl <T, F: FnMut(&str, usize, &Node<T>) -> NodeVisit> NodeVisitor<T> for F {
+    fn visit(&mut self, path: &str, depth: usize, node: &Node<T>) -> NodeVisit {
+        self(path, depth, node)
+    }
+}
+
+/// Trait for types that can be walked over the hierarchy with [`NodeIterTrait::visit_mut`].
+/// It is implemented for all closures with the matching signature.
+pub trait NodeVisitorMut<T> {
+    /// Called on every visited node with path and depth relative to the starting node.
+    fn visit_mut(&mut self, path: &str, depth: usize, node: &mut Node<T>) -> NodeVisit;
+}
+impl <T, F: FnMut(&str, usize, &mut Node<T>) -> NodeVisit> NodeVisitorMut<T> for F {
+    fn visit_mut(&mut self, path: &str, depth: usize, node: &mut Node<T>) -> NodeVisit {
+        self(path, depth, node)
+    }
 }
 
 /// Trait with all node creation implementations.
 /// Lunex abstacts over this trait with another trait.
-pub trait NodeCreationTrait<T> {
-    /// Makes new subnode in this node and returns the new subnodes' name.
+pub trait NodeCreationTrait<T>: NodeGeneralTrait<T> {
+    /// Makes new subnode in this node and returns its handle.
     /// ## 📌 Note
     /// * Use [`NodeCreationTrait::create_node`] for hierarchy creation `(supports path recursion)`
-    fn make_node(&mut self, name: impl Borrow<str>) -> Result<String, NodeError>;
+    fn make_node(&mut self, name: impl Borrow<str>) -> Result<Self::Handle, NodeError>;
     /// ## 🚸 Recursive
-    /// Creates new subnode in this node or any other subnode and returns the new subnodes' name.
+    /// Creates new subnode in this node or any other subnode and returns its handle.
     /// ## 📌 Note
     /// * Use [`NodeCreationTrait::make_node`] for direct creation on this node `(no recursion)`
-    fn create_node(&mut self, path: impl Borrow<str>) -> Result<String, NodeError>;
+    fn create_node(&mut self, path: impl Borrow<str>) -> Result<Self::Handle, NodeError>;
     /// Borrows subnode from this node. If the node doesn't exist, it creates one.
     /// ## 📌 Note
     /// * Use [`NodeCreationTrait::borrow_or_create_node`] for hierarchy retrieval `(supports path recursion)`
-    fn obtain_or_create_node(&mut self, name: impl Borrow<str>) -> Result<&Node<T>, NodeError>;
+    fn obtain_or_create_node(&mut self, name: impl Borrow<str>) -> Result<&Self::Subnode, NodeError>;
     /// Borrows subnode from this node as mut. If the node doesn't exist, it creates one.
     /// ## 📌 Note
     /// * Use [`NodeCreationTrait::borrow_or_create_node_mut`] for hierarchy retrieval `(supports path recursion)`
-    fn obtain_or_create_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut Node<T>, NodeError>;
+    fn obtain_or_create_node_mut(&mut self, name: impl Borrow<str>) -> Result<&mut Self::Subnode, NodeError>;
     /// ## 🚸 Recursive
     /// Borrows subnode from this node or any other subnode. If a node in path doesn't exist, it creates one.
     /// ## 📌 Note
     /// * Use [`NodeCreationTrait::obtain_or_create_node`] for direct retrieval on this node `(no recursion)`
-    fn borrow_or_create_node(&mut self, path: impl Borrow<str>) -> Result<&Node<T>, NodeError>;
+    fn borrow_or_create_node(&mut self, path: impl Borrow<str>) -> Result<&Self::Subnode, NodeError>;
     /// ## 🚸 Recursive
     /// Borrows subnode from this node or any other subnode as mut. If a node in path doesn't exist, it creates one.
     /// ## 📌 Note
     /// * Use [`NodeCreationTrait::obtain_or_create_node_mut`] for direct retrieval on this node `(no recursion)`
-    fn borrow_or_create_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut Node<T>, NodeError>;    
+    fn borrow_or_create_node_mut(&mut self, path: impl Borrow<str>) -> Result<&mut Self::Subnode, NodeError>;    
 }
 
 /// Trait with all node data management implementations.
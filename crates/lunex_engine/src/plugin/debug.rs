@@ -0,0 +1,340 @@
+use bevy::ecs::prelude::*;
+use crate::import::*;
+use crate::{Layout, NodeDataTrait, NodeGeneralTrait, NodeTopDataTrait, Rectangle3D, UiComponent, UiNode, UiTree};
+
+#[cfg(feature = "debug")]
+use std::marker::PhantomData;
+#[cfg(feature = "debug")]
+use bevy::{app::{App, Plugin, PostUpdate}, gizmos::gizmos::Gizmos, render::color::Color, transform::components::GlobalTransform};
+#[cfg(feature = "debug")]
+use crate::YInvert;
+#[cfg(feature = "debug")]
+use super::UiSystems;
+
+
+// #==================#
+// #=== COMPONENTS ===#
+
+/// ## Ui debug
+/// Component toggling the debug overlay of [`UiTree`] on this entity. See [`ui_debug_shapes`] for what is drawn.
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct UiDebug {
+    /// If the overlay is drawn.
+    pub enabled: bool,
+    /// If the margin, padding and content boxes of [`crate::Div`] nodes are drawn.
+    pub boxes: bool,
+    /// If the node names are drawn in the top-left corner of the node outlines.
+    pub names: bool,
+}
+impl UiDebug {
+    /// Creates new enabled overlay with all boxes and names.
+    pub fn new() -> Self {
+        UiDebug { enabled: true, boxes: true, names: true }
+    }
+    /// Replaces the boxes flag with the new value.
+    pub fn boxes(mut self, boxes: bool) -> Self {
+        self.boxes = boxes;
+        self
+    }
+    /// Replaces the names flag with the new value.
+    pub fn names(mut self, names: bool) -> Self {
+        self.names = names;
+        self
+    }
+    /// Enables the overlay if disabled and the other way around.
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+    }
+}
+impl Default for UiDebug {
+    fn default() -> Self {
+        UiDebug::new()
+    }
+}
+
+
+// #==============#
+// #=== SHAPES ===#
+
+/// What part of the node the [`UiDebugShape`] outlines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiDebugKind {
+    /// Computed rectangle of [`Layout::Window`] node.
+    Window,
+    /// Computed rectangle of [`Layout::Solid`] node.
+    Solid,
+    /// Computed rectangle of [`Layout::Div`] node, which is the outer edge of its border.
+    Div,
+    /// Outer edge of the [`crate::Div`] margin.
+    Margin,
+    /// Outer edge of the [`crate::Div`] padding, which is the inner edge of its border.
+    Padding,
+    /// Space left for the content of [`crate::Div`].
+    Content,
+}
+impl UiDebugKind {
+    /// Returns `true` for the [`crate::Div`] box outlines.
+    pub fn is_box(&self) -> bool {
+        matches!(self, UiDebugKind::Margin | UiDebugKind::Padding | UiDebugKind::Content)
+    }
+    /// RGBA color of the outline. Follows [`crate::NiceDisplay`], layouts are bright cyan like [`Layout`]
+    /// and the computed Div boxes are bright magenta like [`Rectangle3D`].
+    pub fn color(&self) -> Vec4 {
+        match self {
+            UiDebugKind::Window | UiDebugKind::Solid | UiDebugKind::Div => Vec4::new(0.0, 1.0, 1.0, 1.0),
+            UiDebugKind::Margin => Vec4::new(1.0, 0.0, 1.0, 0.4),
+            UiDebugKind::Padding => Vec4::new(1.0, 0.0, 1.0, 0.6),
+            UiDebugKind::Content => Vec4::new(1.0, 0.0, 1.0, 0.8),
+        }
+    }
+}
+
+/// Outline of a node or one of its boxes in the coordinates of [`UiTree`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct UiDebugShape {
+    /// Path of the node, usable for labeling the outline.
+    pub path: String,
+    /// What is outlined.
+    pub kind: UiDebugKind,
+    /// Outlined rectangle with the node depth as Z, based on the presented rectangle of the node.
+    pub rectangle: Rectangle3D,
+}
+impl UiDebugShape {
+    /// Returns the name of the outlined node, which is the last segment of its path.
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or_default()
+    }
+}
+
+/// Collects outlines of all computed nodes in [`UiTree`] and the margin, padding and content boxes of its [`crate::Div`] nodes.
+/// ## 📌 Note
+/// * Div boxes are evaluated the same way as during [`crate::UiNodeTreeComputeTrait::compute`], so the tree should be computed first
+pub fn ui_debug_shapes<M: Default + UiComponent, N: Default + UiComponent>(tree: &UiTree<M, N>) -> Vec<UiDebugShape> {
+    let (abs_scale, font_size) = tree.obtain_topdata().map_or((1.0, 16.0), |master| (master.abs_scale, master.font_size));
+    let ancestor_size = tree.node.obtain_data().map_or(Vec2::ZERO, |data| data.rectangle.size);
+    let mut shapes = Vec::new();
+    collect_shapes(&tree.node, ancestor_size, abs_scale, font_size, &mut shapes);
+    shapes
+}
+
+/// ## 🚸 Recursive
+/// Pushes the outlines of this node and its subnodes. Divs inherit the ancestor size like in the layout computation.
+fn collect_shapes<N: Default + UiComponent>(node: &UiNode<N>, ancestor_size: Vec2, abs_scale: f32, font_size: f32, shapes: &mut Vec<UiDebugShape>) {
+    let Some(data) = node.obtain_data() else { return };
+    let font_size = data.font_size.unwrap_or(font_size);
+    let rectangle = data.presented;
+    let path = node.get_path().to_owned();
+
+    let ancestor_size = match &data.layout {
+        Layout::Window(_) => {
+            shapes.push(UiDebugShape { path, kind: UiDebugKind::Window, rectangle });
+            rectangle.size
+        },
+        Layout::Solid(_) => {
+            shapes.push(UiDebugShape { path, kind: UiDebugKind::Solid, rectangle });
+            rectangle.size
+        },
+        Layout::Div(layout) => {
+            let margin = layout.compute_margin(ancestor_size, abs_scale, font_size);
+            let border = layout.compute_border(ancestor_size, abs_scale, font_size);
+            let padding = layout.compute_padding(ancestor_size, abs_scale, font_size);
+            let padding_box = offset(rectangle, -border);
+            shapes.push(UiDebugShape { path: path.clone(), kind: UiDebugKind::Margin, rectangle: offset(rectangle, margin) });
+            shapes.push(UiDebugShape { path: path.clone(), kind: UiDebugKind::Padding, rectangle: padding_box });
+            shapes.push(UiDebugShape { path: path.clone(), kind: UiDebugKind::Content, rectangle: offset(padding_box, -padding) });
+            shapes.push(UiDebugShape { path, kind: UiDebugKind::Div, rectangle });
+            ancestor_size
+        },
+    };
+
+    for subnode in node.nodes.values() {
+        collect_shapes(subnode, ancestor_size, abs_scale, font_size, shapes);
+    }
+}
+
+/// Grows the rectangle by the sides (left, top, right, bottom). Negative sides shrink it.
+fn offset(mut rectangle: Rectangle3D, sides: Vec4) -> Rectangle3D {
+    rectangle.pos.x -= sides.x;
+    rectangle.pos.y -= sides.y;
+    rectangle.size += Vec2::new(sides.x + sides.z, sides.y + sides.w);
+    rectangle
+}
+
+
+// #============#
+// #=== TEXT ===#
+
+/// Strokes of a glyph on a grid 4 wide and 6 tall, with Y pointing down.
+type Glyph = &'static [&'static [(u8, u8)]];
+
+/// Returns the glyph of the character. Letters are uppercase only and unsupported characters are drawn as `?`.
+fn glyph(character: char) -> Glyph {
+    match character.to_ascii_uppercase() {
+        'A' => &[&[(0, 6), (0, 2), (2, 0), (4, 2), (4, 6)], &[(0, 3), (4, 3)]],
+        'B' => &[&[(0, 0), (0, 6), (3, 6), (4, 5), (4, 4), (3, 3), (0, 3)], &[(0, 0), (3, 0), (4, 1), (4, 2), (3, 3)]],
+        'C' => &[&[(4, 0), (0, 0), (0, 6), (4, 6)]],
+        'D' => &[&[(0, 0), (0, 6), (2, 6), (4, 4), (4, 2), (2, 0), (0, 0)]],
+        'E' => &[&[(4, 0), (0, 0), (0, 6), (4, 6)], &[(0, 3), (3, 3)]],
+        'F' => &[&[(4, 0), (0, 0), (0, 6)], &[(0, 3), (3, 3)]],
+        'G' => &[&[(4, 0), (0, 0), (0, 6), (4, 6), (4, 3), (2, 3)]],
+        'H' => &[&[(0, 0), (0, 6)], &[(4, 0), (4, 6)], &[(0, 3), (4, 3)]],
+        'I' => &[&[(0, 0), (4, 0)], &[(2, 0), (2, 6)], &[(0, 6), (4, 6)]],
+        'J' => &[&[(4, 0), (4, 6), (0, 6), (0, 4)]],
+        'K' => &[&[(0, 0), (0, 6)], &[(4, 0), (0, 3), (4, 6)]],
+        'L' => &[&[(0, 0), (0, 6), (4, 6)]],
+        'M' => &[&[(0, 6), (0, 0), (2, 3), (4, 0), (4, 6)]],
+        'N' => &[&[(0, 6), (0, 0), (4, 6), (4, 0)]],
+        'O' => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)]],
+        'P' => &[&[(0, 6), (0, 0), (4, 0), (4, 3), (0, 3)]],
+        'Q' => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)], &[(2, 4), (4, 6)]],
+        'R' => &[&[(0, 6), (0, 0), (4, 0), (4, 3), (0, 3), (4, 6)]],
+        'S' => &[&[(4, 1), (3, 0), (1, 0), (0, 1), (0, 2), (1, 3), (3, 3), (4, 4), (4, 5), (3, 6), (1, 6), (0, 5)]],
+        'T' => &[&[(0, 0), (4, 0)], &[(2, 0), (2, 6)]],
+        'U' => &[&[(0, 0), (0, 6), (4, 6), (4, 0)]],
+        'V' => &[&[(0, 0), (2, 6), (4, 0)]],
+        'W' => &[&[(0, 0), (1, 6), (2, 3), (3, 6), (4, 0)]],
+        'X' => &[&[(0, 0), (4, 6)], &[(4, 0), (0, 6)]],
+        'Y' => &[&[(0, 0), (2, 3), (4, 0)], &[(2, 3), (2, 6)]],
+        'Z' => &[&[(0, 0), (4, 0), (0, 6), (4, 6)]],
+        '0' => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0), (4, 6)]],
+        '1' => &[&[(1, 1), (2, 0), (2, 6)], &[(0, 6), (4, 6)]],
+        '2' => &[&[(0, 0), (4, 0), (4, 3), (0, 3), (0, 6), (4, 6)]],
+        '3' => &[&[(0, 0), (4, 0), (4, 6), (0, 6)], &[(0, 3), (4, 3)]],
+        '4' => &[&[(0, 0), (0, 3), (4, 3)], &[(4, 0), (4, 6)]],
+        '5' => &[&[(4, 0), (0, 0), (0, 3), (4, 3), (4, 6), (0, 6)]],
+        '6' => &[&[(4, 0), (0, 0), (0, 6), (4, 6), (4, 3), (0, 3)]],
+        '7' => &[&[(0, 0), (4, 0), (2, 6)]],
+        '8' => &[&[(0, 0), (4, 0), (4, 6), (0, 6), (0, 0)], &[(0, 3), (4, 3)]],
+        '9' => &[&[(4, 3), (0, 3), (0, 0), (4, 0), (4, 6), (0, 6)]],
+        '_' => &[&[(0, 6), (4, 6)]],
+        '-' => &[&[(1, 3), (3, 3)]],
+        '.' => &[&[(2, 5), (2, 6)]],
+        ':' => &[&[(2, 1), (2, 2)], &[(2, 4), (2, 5)]],
+        '|' => &[&[(2, 0), (2, 6)]],
+        '#' => &[&[(1, 0), (1, 6)], &[(3, 0), (3, 6)], &[(0, 2), (4, 2)], &[(0, 4), (4, 4)]],
+        ' ' => &[],
+        _ => &[&[(0, 1), (1, 0), (3, 0), (4, 1), (4, 2), (2, 3), (2, 4)], &[(2, 5), (2, 6)]],
+    }
+}
+
+/// Returns line strips writing the text in a simple stroke font, so it can be drawn with gizmos.
+/// The text starts at the top-left corner `pos` and each character is `height` tall, in the coordinates of [`UiTree`].
+/// ## 📌 Note
+/// * Letters are drawn uppercase, unsupported characters are drawn as `?`
+pub fn ui_debug_text(text: &str, pos: Vec2, height: f32) -> Vec<Vec<Vec2>> {
+    let scale = height / 6.0;
+    let mut strips = Vec::new();
+    for (index, character) in text.chars().enumerate() {
+        let origin = pos + Vec2::new(index as f32 * 6.0 * scale, 0.0);
+        for stroke in glyph(character) {
+            strips.push(stroke.iter().map(|&(x, y)| origin + Vec2::new(x as f32, y as f32) * scale).collect());
+        }
+    }
+    strips
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// Draws the outlines from [`ui_debug_shapes`] of all [`UiTree`]s with enabled [`UiDebug`] using gizmos.
+/// Node names are written with [`ui_debug_text`] in the top-left corner of the node outlines, half the tree font size tall.
+#[cfg(feature = "debug")]
+pub fn draw_ui_debug<M: Default + UiComponent, N: Default + UiComponent>(
+    trees: Query<(&UiTree<M, N>, &UiDebug, &GlobalTransform)>,
+    mut gizmos: Gizmos,
+) {
+    for (tree, debug, transform) in &trees {
+        if !debug.enabled { continue }
+        let height = tree.obtain_topdata().map_or(16.0, |master| master.font_size) * 0.5;
+        for shape in ui_debug_shapes(tree) {
+            if !debug.boxes && shape.kind.is_box() { continue }
+            let rectangle = shape.rectangle;
+            let corner = |x: f32, y: f32| transform.transform_point((rectangle.pos + Vec3::new(x, y, 0.0)).invert_y());
+            let points = [
+                corner(0.0, 0.0),
+                corner(rectangle.size.x, 0.0),
+                corner(rectangle.size.x, rectangle.size.y),
+                corner(0.0, rectangle.size.y),
+                corner(0.0, 0.0),
+            ];
+            let color = shape.kind.color();
+            let color = Color::rgba(color.x, color.y, color.z, color.w);
+            gizmos.linestrip(points, color);
+
+            if !debug.names || shape.kind.is_box() { continue }
+            for strip in ui_debug_text(shape.name(), rectangle.pos.truncate() + height * 0.25, height) {
+                gizmos.linestrip(strip.into_iter().map(|point| transform.transform_point(point.extend(rectangle.pos.z).invert_y())), color);
+            }
+        }
+    }
+}
+
+/// ## Ui debug plugin
+/// Opt-in plugin drawing the debug overlay of [`UiTree<M, N>`] on entities with [`UiDebug`].
+/// ## 📌 Note
+/// * Requires the `debug` feature and the Bevy gizmo plugin, which is part of `DefaultPlugins`
+#[cfg(feature = "debug")]
+pub struct UiDebugPlugin<M: Default + UiComponent = crate::NoData, N: Default + UiComponent = crate::NoData>(PhantomData<(M, N)>);
+#[cfg(feature = "debug")]
+impl <M: Default + UiComponent, N: Default + UiComponent> UiDebugPlugin<M, N> {
+    /// Creates new plugin.
+    pub fn new() -> Self {
+        UiDebugPlugin(PhantomData)
+    }
+}
+#[cfg(feature = "debug")]
+impl <M: Default + UiComponent, N: Default + UiComponent> Default for UiDebugPlugin<M, N> {
+    fn default() -> Self {
+        UiDebugPlugin::new()
+    }
+}
+#[cfg(feature = "debug")]
+impl <M: Default + UiComponent, N: Default + UiComponent> Plugin for UiDebugPlugin<M, N> {
+    fn build(&self, app: &mut App) {
+        app.add_systems(PostUpdate, draw_ui_debug::<M, N>.after(UiSystems::Compute));
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::{ui_debug_shapes, ui_debug_text, UiDebugKind};
+    use crate::prelude::*;
+    use crate::import::*;
+    use crate::{NodeDataTrait, Rectangle2D, Window};
+
+    #[test]
+    fn debug_shapes () {
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().size(Abs(Vec2::new(100.0, 100.0))).build(&mut tree, "panel").unwrap();
+        Div::new().pad(Abs(Vec4::splat(5.0))).border(Abs(Vec4::splat(2.0))).margin(Abs(Vec4::splat(10.0))).build(&mut tree, "panel/div").unwrap();
+        tree.borrow_data_mut("panel/div").unwrap().unwrap().content_size = Vec2::new(20.0, 10.0);
+        tree.compute(Rectangle2D::new().with_size((800.0, 600.0)).into());
+
+        let shapes = ui_debug_shapes(&tree);
+        let find = |kind| shapes.iter().find(|shape| shape.path == "panel/div" && shape.kind == kind).unwrap().rectangle;
+        let div = find(UiDebugKind::Div);
+        assert_eq!(div.size, Vec2::new(34.0, 24.0));
+        assert_eq!(find(UiDebugKind::Padding).size, Vec2::new(30.0, 20.0));
+        assert_eq!(find(UiDebugKind::Content).size, Vec2::new(20.0, 10.0));
+        assert_eq!(find(UiDebugKind::Content).pos, div.pos + Vec3::new(7.0, 7.0, 0.0));
+        assert_eq!(find(UiDebugKind::Margin).size, Vec2::new(54.0, 44.0));
+        assert!(shapes.iter().any(|shape| shape.path == "panel" && shape.kind == UiDebugKind::Window));
+        assert_eq!(shapes.iter().find(|shape| shape.kind == UiDebugKind::Div).unwrap().name(), "div");
+    }
+
+    #[test]
+    fn debug_text () {
+        let strips = ui_debug_text("t1", Vec2::new(10.0, 20.0), 12.0);
+        assert_eq!(strips, ui_debug_text("T1", Vec2::new(10.0, 20.0), 12.0));
+        assert_eq!(strips.len(), 4);
+        assert_eq!(strips[0], vec![Vec2::new(10.0, 20.0), Vec2::new(18.0, 20.0)]);
+        assert_eq!(strips[3], vec![Vec2::new(22.0, 32.0), Vec2::new(30.0, 32.0)]);
+        assert!(ui_debug_text(" ", Vec2::ZERO, 12.0).is_empty());
+        assert!(!ui_debug_text("~", Vec2::ZERO, 12.0).is_empty());
+    }
+}
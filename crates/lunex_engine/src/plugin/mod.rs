@@ -0,0 +1,18 @@
+mod debug;
+pub use debug::*;
+
+mod structs;
+pub use structs::*;
+
+mod systems;
+pub use systems::*;
+
+pub mod prelude {
+    pub use super::{UiPlugin, UiSystems};
+    pub use super::{UiLink, UiSource, Dimension};
+    pub use super::UiRectangleChanged;
+    #[cfg(feature = "render")]
+    pub use super::UiAnchor;
+    #[cfg(feature = "debug")]
+    pub use super::{UiDebug, UiDebugPlugin};
+}
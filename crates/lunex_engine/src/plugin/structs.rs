@@ -0,0 +1,150 @@
+use std::marker::PhantomData;
+use bevy::app::{App, Plugin, PostUpdate};
+use bevy::ecs::prelude::*;
+use bevy::math::Vec2;
+use bevy::transform::TransformSystem;
+use crate::import::*;
+use crate::{Rectangle3D, UiComponent};
+use super::{compute_ui, link_ui, sync_ui, tick_ui};
+#[cfg(feature = "render")]
+use super::anchor_ui;
+
+
+// #==================#
+// #=== COMPONENTS ===#
+
+/// ## Ui link
+/// Component linking an entity to the node at the path inside [`crate::UiTree`] of its parent entity.
+/// The node is created if missing and removed when the entity is despawned, see [`super::link_ui`].
+/// The `Transform` and [`Dimension`] of the entity are updated from the presented rectangle of the node, see [`crate::NodeData::presented`].
+#[derive(Component, Debug, Default, Clone, PartialEq)]
+pub struct UiLink {
+    /// Path of the linked node.
+    pub path: String,
+}
+impl UiLink {
+    /// Creates new link to the node at the path.
+    pub fn path(path: impl Borrow<str>) -> Self {
+        UiLink { path: path.borrow().to_owned() }
+    }
+}
+
+/// ## Dimension
+/// Size of the entity. Written by [`UiPlugin`] on linked entities from the presented rectangle of their node.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub struct Dimension {
+    pub size: Vec2,
+}
+impl Dimension {
+    /// Creates new dimension with the size.
+    pub fn new(size: impl Into<Vec2>) -> Self {
+        Dimension { size: size.into() }
+    }
+}
+
+/// ## Ui anchor
+/// Component making the `Window` node linked by [`UiLink`] follow the target entity as seen through the camera entity.
+/// Feeds [`crate::WorldAnchor`] of the window every frame, see [`super::anchor_ui`].
+/// ## 📌 Note
+/// * Requires the `render` feature
+#[cfg(feature = "render")]
+#[derive(Component, Debug, Clone, Copy, PartialEq)]
+pub struct UiAnchor {
+    /// Entity with `GlobalTransform` to follow.
+    pub target: Entity,
+    /// Entity with `Camera` and `GlobalTransform` to project through.
+    pub camera: Entity,
+}
+#[cfg(feature = "render")]
+impl UiAnchor {
+    /// Creates new anchor following the target through the camera.
+    pub fn new(target: Entity, camera: Entity) -> Self {
+        UiAnchor { target, camera }
+    }
+}
+
+/// ## Ui source
+/// Component on the entity with [`crate::UiTree`] defining where the size of the root rectangle comes from.
+/// The root rectangle is centered on the entity, so the tree is drawn around its `Transform`.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq)]
+pub enum UiSource {
+    /// Size of the window marked with `PrimaryWindow`.
+    #[default]
+    PrimaryWindow,
+    /// Size of the window entity.
+    Window(Entity),
+    /// Logical viewport size of the camera entity.
+    /// Without the `render` feature, or before the camera knows its render target, the size is taken from its [`Dimension`].
+    Camera(Entity),
+    /// Fixed size.
+    Size(Vec2),
+}
+
+
+// #==============#
+// #=== EVENTS ===#
+
+/// ## Ui rectangle changed
+/// Event sent by [`super::compute_ui`] for every node whose computed rectangle changed.
+/// Newly created nodes are reported with the default old rectangle.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct UiRectangleChanged {
+    /// Entity with the [`crate::UiTree`].
+    pub tree: Entity,
+    /// Path of the node.
+    pub path: String,
+    /// Rectangle from the previous computation.
+    pub old: Rectangle3D,
+    /// Newly computed rectangle.
+    pub new: Rectangle3D,
+}
+
+
+// #==============#
+// #=== PLUGIN ===#
+
+/// System sets of [`UiPlugin`], running in `PostUpdate` before the transform propagation.
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UiSystems {
+    /// Creates, updates and removes nodes linked to entities with [`UiLink`].
+    Link,
+    /// Advances timelines, tweens and transitions of all [`crate::UiTree`]s.
+    Animate,
+    /// Computes the layout of all [`crate::UiTree`]s.
+    Compute,
+    /// Writes the presented rectangles to the linked entities.
+    Sync,
+}
+
+/// ## Ui plugin
+/// Plugin keeping [`crate::UiTree<M, N>`] components in sync with their children linked by [`UiLink`],
+/// computing them and writing the results back to the linked entities.
+/// ## 📌 Note
+/// * Add one plugin for every `M` and `N` combination you use
+pub struct UiPlugin<M: Default + UiComponent = crate::NoData, N: Default + UiComponent = crate::NoData>(PhantomData<(M, N)>);
+impl <M: Default + UiComponent, N: Default + UiComponent> UiPlugin<M, N> {
+    /// Creates new plugin.
+    pub fn new() -> Self {
+        UiPlugin(PhantomData)
+    }
+}
+impl <M: Default + UiComponent, N: Default + UiComponent> Default for UiPlugin<M, N> {
+    fn default() -> Self {
+        UiPlugin::new()
+    }
+}
+impl <M: Default + UiComponent, N: Default + UiComponent + Clone> Plugin for UiPlugin<M, N> {
+    fn build(&self, app: &mut App) {
+        app.add_event::<UiRectangleChanged>()
+            .configure_sets(PostUpdate, (UiSystems::Link, UiSystems::Animate, UiSystems::Compute, UiSystems::Sync).chain().before(TransformSystem::TransformPropagate))
+            .add_systems(PostUpdate, (
+                link_ui::<M, N>.in_set(UiSystems::Link),
+                tick_ui::<M, N>.in_set(UiSystems::Animate),
+                compute_ui::<M, N>.in_set(UiSystems::Compute),
+                sync_ui::<M, N>.in_set(UiSystems::Sync),
+            ));
+
+        #[cfg(feature = "render")]
+        app.add_systems(PostUpdate, anchor_ui::<M, N>.in_set(UiSystems::Link).after(link_ui::<M, N>));
+    }
+}
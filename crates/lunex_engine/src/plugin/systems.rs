@@ -0,0 +1,323 @@
+use bevy::ecs::prelude::*;
+use bevy::hierarchy::{Children, Parent};
+use bevy::math::Vec2;
+use bevy::time::Time;
+use bevy::transform::components::Transform;
+use bevy::window::{Window, PrimaryWindow};
+use crate::import::HashMap;
+use crate::{FlexBox, Layout, NodeDataTrait, NodeGeneralTrait, NodeIterTrait, Rectangle2D, Rectangle3D, UiComponent, UiNodeCreationTrait, UiNodeTreeComputeTrait, UiNodeTreeTickTrait, UiTree, YInvert};
+use super::{Dimension, UiLink, UiRectangleChanged, UiSource};
+
+#[cfg(feature = "render")]
+use bevy::{render::camera::Camera, transform::components::GlobalTransform};
+#[cfg(feature = "render")]
+use crate::WorldAnchor;
+#[cfg(feature = "render")]
+use super::UiAnchor;
+
+
+// #===============#
+// #=== SYSTEMS ===#
+
+/// Keeps the nodes of [`UiTree`]s in sync with the child entities linked to them by [`UiLink`].
+/// * Missing nodes (and their ancestors) are created with the default [`crate::NodeData`]
+/// * `Layout`, `FlexBox` and `N` components of the entity are copied into the node data when they change
+/// * The node is removed when the entity is despawned, loses its [`UiLink`] or links a different path
+/// ## 📌 Note
+/// * Only components present on the entity are copied, the rest of the node data is left untouched
+#[allow(clippy::type_complexity)]
+pub fn link_ui<M: Default + UiComponent, N: Default + UiComponent + Clone>(
+    mut trees: Query<&mut UiTree<M, N>>,
+    links: Query<(Entity, &UiLink, &Parent, Option<&Layout>, Option<&FlexBox>, Option<&N>), Or<(Changed<UiLink>, Changed<Parent>, Changed<Layout>, Changed<FlexBox>, Changed<N>)>>,
+    mut removed: RemovedComponents<UiLink>,
+    mut linked: Local<HashMap<Entity, (Entity, String)>>,
+) {
+    for entity in removed.read() {
+        let Some((tree, path)) = linked.swap_remove(&entity) else { continue };
+        if let Ok(mut tree) = trees.get_mut(tree) {
+            let _ = tree.remove_node(path);
+        }
+    }
+
+    for (entity, link, parent, layout, stack, data) in &links {
+        // Unlink the previously linked node
+        if let Some((old_tree, old_path)) = linked.get(&entity) {
+            if *old_tree != parent.get() || *old_path != link.path {
+                if let Ok(mut old_tree) = trees.get_mut(*old_tree) {
+                    let _ = old_tree.remove_node(old_path.as_str());
+                }
+                linked.swap_remove(&entity);
+            }
+        }
+
+        let Ok(mut tree) = trees.get_mut(parent.get()) else { continue };
+        let Ok(node) = tree.borrow_or_create_ui_node_mut(link.path.as_str()) else { continue };
+        let Some(node_data) = node.obtain_data_mut() else { continue };
+        if let Some(layout) = layout { node_data.layout = *layout }
+        if let Some(stack) = stack { node_data.stack = *stack }
+        if let Some(data) = data { node_data.data = Some(data.clone()) }
+
+        linked.insert(entity, (parent.get(), link.path.clone()));
+    }
+}
+
+/// Feeds [`crate::NodeData::anchor`] of nodes linked to entities with [`UiAnchor`] from their target and camera.
+/// The tree is marked as changed only when the anchor actually moved.
+/// ## 📌 Note
+/// * Global transforms from the previous frame are used, as the system runs before the transform propagation
+#[cfg(feature = "render")]
+pub fn anchor_ui<M: Default + UiComponent, N: Default + UiComponent>(
+    anchors: Query<(&UiAnchor, &UiLink, &Parent)>,
+    targets: Query<&GlobalTransform>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut trees: Query<&mut UiTree<M, N>>,
+) {
+    for (anchor, link, parent) in &anchors {
+        let Ok(target) = targets.get(anchor.target) else { continue };
+        let Ok((camera, transform)) = cameras.get(anchor.camera) else { continue };
+        let Ok(mut tree) = trees.get_mut(parent.get()) else { continue };
+
+        let world = Some(WorldAnchor::new(target.translation(), transform.compute_matrix(), camera.projection_matrix()));
+        let Ok(Some(data)) = tree.bypass_change_detection().borrow_data_mut(link.path.as_str()) else { continue };
+        if data.anchor != world {
+            data.anchor = world;
+            tree.set_changed();
+        }
+    }
+}
+
+/// Advances timelines, tweens and transitions of all [`UiTree`]s by the frame time.
+/// The tree is marked as changed only while something animates, so idle trees are not recomputed.
+pub fn tick_ui<M: Default + UiComponent, N: Default + UiComponent>(
+    time: Res<Time>,
+    mut query: Query<&mut UiTree<M, N>>,
+) {
+    let delta = time.delta_seconds();
+    for mut tree in &mut query {
+        if tree.bypass_change_detection().tick(delta) {
+            tree.set_changed();
+        }
+    }
+}
+
+/// Computes all [`UiTree`]s with the size taken from their [`UiSource`] and sends [`UiRectangleChanged`] for every node that moved or resized.
+/// Trees with unavailable source (missing window or camera) are skipped.
+/// ## 📌 Note
+/// * The tree is computed only if the [`UiTree`] component (including its [`crate::MasterData`]) changed or the size of its source changed,
+///   so trees that stay idle cost only a size check per frame
+pub fn compute_ui<M: Default + UiComponent, N: Default + UiComponent>(
+    mut query: Query<(Entity, &mut UiTree<M, N>, &UiSource)>,
+    primary: Query<&Window, With<PrimaryWindow>>,
+    windows: Query<&Window>,
+    dimensions: Query<&Dimension>,
+    #[cfg(feature = "render")]
+    cameras: Query<&Camera>,
+    mut events: EventWriter<UiRectangleChanged>,
+    mut sizes: Local<HashMap<Entity, Vec2>>,
+) {
+    sizes.retain(|entity, _| query.contains(*entity));
+    for (entity, mut tree, source) in &mut query {
+        let size = match source {
+            UiSource::PrimaryWindow => primary.get_single().ok().map(|window| Vec2::new(window.width(), window.height())),
+            UiSource::Window(entity) => windows.get(*entity).ok().map(|window| Vec2::new(window.width(), window.height())),
+            #[cfg(feature = "render")]
+            UiSource::Camera(entity) => cameras.get(*entity).ok().and_then(Camera::logical_viewport_size).or_else(|| dimensions.get(*entity).ok().map(|dimension| dimension.size)),
+            #[cfg(not(feature = "render"))]
+            UiSource::Camera(entity) => dimensions.get(*entity).ok().map(|dimension| dimension.size),
+            UiSource::Size(size) => Some(*size),
+        };
+        let Some(size) = size else { continue };
+        if !tree.is_changed() && sizes.get(&entity) == Some(&size) { continue }
+        sizes.insert(entity, size);
+
+        let old: HashMap<String, Rectangle3D> = tree.iter_dfs().filter_map(|(path, _, node)| node.data.as_ref().map(|data| (path, data.rectangle))).collect();
+        tree.compute(Rectangle2D::new().with_pos(-size / 2.0).with_size(size).into());
+
+        events.send_batch(tree.iter_dfs().filter_map(|(path, _, node)| {
+            let new = node.data.as_ref()?.rectangle;
+            let old = old.get(&path).copied().unwrap_or_default();
+            (old != new).then_some(UiRectangleChanged { tree: entity, path, old, new })
+        }));
+    }
+}
+
+/// Writes the presented rectangles of [`UiTree`]s to the `Transform` and [`Dimension`] of their children with [`UiLink`].
+/// Translation points to the top-left corner of the node, with Y flipped by [`YInvert`] and depth as Z.
+/// ## 📌 Note
+/// * Only trees that changed since the last run are synced
+/// * Components are only written when the value differs, so change detection is not triggered needlessly
+#[allow(clippy::type_complexity)]
+pub fn sync_ui<M: Default + UiComponent, N: Default + UiComponent>(
+    trees: Query<(&UiTree<M, N>, &Children), Changed<UiTree<M, N>>>,
+    mut links: Query<(&UiLink, &mut Transform, Option<&mut Dimension>)>,
+) {
+    for (tree, children) in &trees {
+        for child in children.iter() {
+            let Ok((link, mut transform, dimension)) = links.get_mut(*child) else { continue };
+            let Ok(Some(data)) = tree.borrow_data(link.path.as_str()) else { continue };
+
+            let translation = data.presented.pos.invert_y();
+            if transform.translation != translation {
+                transform.translation = translation;
+            }
+            if let Some(mut dimension) = dimension {
+                dimension.set_if_neq(Dimension::new(data.presented.size));
+            }
+        }
+    }
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use bevy::app::App;
+    use bevy::ecs::component::Component;
+    use bevy::hierarchy::{BuildWorldChildren, DespawnRecursiveExt};
+    use bevy::transform::components::Transform;
+    use bevy::MinimalPlugins;
+    use crate::prelude::*;
+    use crate::import::*;
+    use bevy::ecs::event::Events;
+    use crate::{NodeDataTrait, UiNodeDataTrait, UiPlugin, UiLink, UiSource, UiRectangleChanged, Dimension, Layout, Window};
+
+    #[derive(Component, Debug, Default, Clone, PartialEq)]
+    struct Tag(u32);
+
+    #[test]
+    fn plugin () {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UiPlugin::<NoData, NoData>::new()));
+
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().pos(Abs(Vec2::new(100.0, 50.0))).size(Prc(50.0)).build(&mut tree, "panel").unwrap();
+        Window::new().size(Prc(Vec2::new(50.0, 10.0))).build(&mut tree, "panel/button").unwrap();
+
+        let panel = app.world.spawn((UiLink::path("panel"), Transform::default(), Dimension::default())).id();
+        let button = app.world.spawn((UiLink::path("panel/button"), Transform::default())).id();
+        app.world.spawn((tree, UiSource::Size(Vec2::new(800.0, 600.0)))).push_children(&[panel, button]);
+
+        app.update();
+
+        assert_eq!(app.world.get::<Transform>(panel).unwrap().translation, Vec3::new(-300.0, 250.0, 1.0));
+        assert_eq!(app.world.get::<Dimension>(panel).unwrap().size, Vec2::new(400.0, 300.0));
+        assert_eq!(app.world.get::<Transform>(button).unwrap().translation, Vec3::new(-300.0, 250.0, 2.0));
+    }
+
+    #[test]
+    fn link () {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UiPlugin::<NoData, Tag>::new()));
+
+        let window: Layout = Window::new().size(Abs(Vec2::new(20.0, 10.0))).into();
+        let label = app.world.spawn((UiLink::path("menu/label"), Transform::default(), window, Tag(1))).id();
+        let tree = app.world.spawn((UiTree::<NoData, Tag>::new("Menu"), UiSource::Size(Vec2::new(800.0, 600.0)))).push_children(&[label]).id();
+
+        app.update();
+
+        // Missing node and its ancestor are created with entity data
+        let ui = app.world.get::<UiTree<NoData, Tag>>(tree).unwrap();
+        assert!(ui.borrow_data("menu").unwrap().is_some());
+        assert_eq!(ui.borrow_data("menu/label").unwrap().unwrap().layout, window);
+        assert_eq!(ui.borrow_ui_data("menu/label"), Ok(Some(&Tag(1))));
+        assert_eq!(app.world.get::<Transform>(label).unwrap().translation, Vec3::new(-400.0, 300.0, 2.0));
+
+        // Changes are synced
+        app.world.get_mut::<Tag>(label).unwrap().0 = 2;
+        app.update();
+        let ui = app.world.get::<UiTree<NoData, Tag>>(tree).unwrap();
+        assert_eq!(ui.borrow_ui_data("menu/label"), Ok(Some(&Tag(2))));
+
+        // Despawning removes the node
+        app.world.entity_mut(label).despawn_recursive();
+        app.update();
+        let ui = app.world.get::<UiTree<NoData, Tag>>(tree).unwrap();
+        assert!(ui.borrow_node("menu/label").is_err());
+        assert!(ui.borrow_node("menu").is_ok());
+    }
+
+    #[test]
+    fn change_detection () {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UiPlugin::<NoData, NoData>::new()));
+
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().size(Prc(50.0)).build(&mut tree, "panel").unwrap();
+        let entity = app.world.spawn((tree, UiSource::Size(Vec2::new(800.0, 600.0)))).id();
+        let mut reader = app.world.resource::<Events<UiRectangleChanged>>().get_reader();
+        let mut events = |app: &App| reader.read(app.world.resource::<Events<UiRectangleChanged>>()).cloned().collect::<Vec<_>>();
+
+        app.update();
+        let changed = events(&app);
+        let panel = changed.iter().find(|event| event.path == "panel").unwrap();
+        assert_eq!((panel.tree, panel.new.size), (entity, Vec2::new(400.0, 300.0)));
+
+        // Idle tree is not computed again
+        let ticks = app.world.entity(entity).get_change_ticks::<UiTree>().unwrap();
+        app.update();
+        assert!(events(&app).is_empty());
+        assert_eq!(app.world.entity(entity).get_change_ticks::<UiTree>().unwrap().last_changed_tick(), ticks.last_changed_tick());
+
+        // Resizing the source recomputes the tree
+        *app.world.get_mut::<UiSource>(entity).unwrap() = UiSource::Size(Vec2::new(400.0, 600.0));
+        app.update();
+        let changed = events(&app);
+        assert_eq!(changed.iter().find(|event| event.path == "panel").unwrap().old.size, Vec2::new(400.0, 300.0));
+        assert_eq!(changed.iter().find(|event| event.path == "panel").unwrap().new.size, Vec2::new(200.0, 300.0));
+    }
+
+    #[cfg(feature = "render")]
+    #[test]
+    fn anchor () {
+        use bevy::render::camera::Camera;
+        use bevy::transform::components::GlobalTransform;
+        use crate::{UiAnchor, WorldAnchor};
+
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UiPlugin::<NoData, NoData>::new()));
+
+        let target = app.world.spawn(GlobalTransform::from_translation(Vec3::new(1.0, 2.0, 3.0))).id();
+        let camera_transform = GlobalTransform::from_translation(Vec3::new(0.0, 0.0, 10.0));
+        let camera = app.world.spawn((Camera::default(), camera_transform)).id();
+        let projection = Camera::default().projection_matrix();
+
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().size(Abs(Vec2::new(50.0, 10.0))).build(&mut tree, "nameplate").unwrap();
+        let nameplate = app.world.spawn((UiLink::path("nameplate"), UiAnchor::new(target, camera))).id();
+        let entity = app.world.spawn((tree, UiSource::Size(Vec2::new(800.0, 600.0)))).push_children(&[nameplate]).id();
+        let anchor = |app: &App| app.world.get::<UiTree>(entity).unwrap().borrow_data("nameplate").unwrap().unwrap().anchor;
+
+        app.update();
+        assert_eq!(anchor(&app), Some(WorldAnchor::new(Vec3::new(1.0, 2.0, 3.0), camera_transform.compute_matrix(), projection)));
+
+        // Still anchor doesn't touch the tree
+        let ticks = app.world.entity(entity).get_change_ticks::<UiTree>().unwrap();
+        app.update();
+        assert_eq!(app.world.entity(entity).get_change_ticks::<UiTree>().unwrap().last_changed_tick(), ticks.last_changed_tick());
+
+        // Moving target moves the anchor
+        *app.world.get_mut::<GlobalTransform>(target).unwrap() = GlobalTransform::from_translation(Vec3::new(-1.0, 0.0, 0.0));
+        app.update();
+        assert_eq!(anchor(&app).unwrap().point, Vec3::new(-1.0, 0.0, 0.0));
+        assert_ne!(app.world.entity(entity).get_change_ticks::<UiTree>().unwrap().last_changed_tick(), ticks.last_changed_tick());
+    }
+
+    #[test]
+    fn camera_source () {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, UiPlugin::<NoData, NoData>::new()));
+
+        // Camera without a known render target falls back to its dimension
+        let camera = app.world.spawn(Dimension::new((640.0, 480.0))).id();
+        #[cfg(feature = "render")]
+        app.world.entity_mut(camera).insert(bevy::render::camera::Camera::default());
+
+        let mut tree: UiTree = UiTree::new("Menu");
+        Window::new().size(Prc(50.0)).build(&mut tree, "panel").unwrap();
+        let entity = app.world.spawn((tree, UiSource::Camera(camera))).id();
+        app.update();
+        assert_eq!(app.world.get::<UiTree>(entity).unwrap().borrow_data("panel").unwrap().unwrap().rectangle.size, Vec2::new(320.0, 240.0));
+    }
+}
@@ -0,0 +1,2 @@
+mod structs;
+pub use structs::*;
@@ -0,0 +1,192 @@
+use std::fmt;
+use crate::import::*;
+use crate::{Rectangle2D, Rectangle3D, UiComponent, UiTree, UiNodeTreeComputeTrait, NodeIterTrait};
+
+
+// #==================#
+// #=== ERROR TYPE ===#
+
+/// ## Snapshot error
+/// Error type indicating that a [`LayoutSnapshot`] line can't be parsed. Holds the offending line.
+#[derive(Debug, Error, Clone, PartialEq)]
+#[error("Invalid snapshot line: '{0}'")]
+pub struct SnapshotError(pub String);
+
+
+// #================#
+// #=== SNAPSHOT ===#
+
+/// A single line of [`LayoutSnapshot`]. Path of the node and its computed rectangle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotEntry {
+    pub path: String,
+    pub rectangle: Rectangle3D,
+}
+
+/// A stable textual snapshot of a computed [`UiTree`] layout, meant for locking in layout behaviour in tests.
+/// ## 📏 Format
+/// One node per line in depth-first order. Numbers are rounded to 2 decimal places and no colors are used.
+/// ```text
+/// Window | pos: 0.00 0.00 1.00 | size: 800.00 600.00
+/// Window/Div | pos: 0.00 0.00 2.00 | size: 120.00 40.00
+/// ```
+/// ## 📌 Note
+/// * Only position and size are captured, rotation is not part of the snapshot
+/// * Nodes without data are skipped
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct LayoutSnapshot {
+    pub entries: Vec<SnapshotEntry>,
+}
+impl LayoutSnapshot {
+    /// Captures rectangles of all nodes as they were last computed.
+    pub fn capture<M: Default + UiComponent, N: Default + UiComponent>(tree: &UiTree<M, N>) -> Self {
+        let entries = tree.iter_dfs().filter_map(|(path, _, node)| {
+            node.data.as_ref().map(|data| SnapshotEntry { path, rectangle: round(data.rectangle) })
+        }).collect();
+        LayoutSnapshot { entries }
+    }
+    /// Computes the tree inside root of the given size and captures the result.
+    pub fn compute<M: Default + UiComponent, N: Default + UiComponent>(tree: &mut UiTree<M, N>, size: impl Into<Vec2>) -> Self {
+        tree.compute(Rectangle2D::new().with_size(size).into());
+        LayoutSnapshot::capture(tree)
+    }
+    /// Parses the snapshot from the text format. Surrounding whitespace and empty lines are ignored.
+    pub fn parse(text: &str) -> Result<Self, SnapshotError> {
+        let mut entries = Vec::new();
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let invalid = || SnapshotError(line.to_owned());
+            let mut parts = line.rsplitn(3, " | ");
+            let (Some(size), Some(pos), Some(path)) = (parts.next(), parts.next(), parts.next()) else { return Err(invalid()) };
+            let pos = parse_floats(pos.strip_prefix("pos:").ok_or_else(invalid)?).ok_or_else(invalid)?;
+            let size = parse_floats(size.strip_prefix("size:").ok_or_else(invalid)?).ok_or_else(invalid)?;
+            let (&[x, y, z], &[w, h]) = (pos.as_slice(), size.as_slice()) else { return Err(invalid()) };
+            entries.push(SnapshotEntry {
+                path: path.to_owned(),
+                rectangle: Rectangle3D { pos: Vec3::new(x, y, z), size: Vec2::new(w, h), ..Default::default() },
+            });
+        }
+        Ok(LayoutSnapshot { entries })
+    }
+    /// Compares this (actual) snapshot against the expected one and returns a description of every mismatch.
+    /// Position and size components are equal if they differ by at most `tolerance`.
+    pub fn compare(&self, expected: &LayoutSnapshot, tolerance: f32) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        let actual: HashMap<&str, &Rectangle3D> = self.entries.iter().map(|entry| (entry.path.as_str(), &entry.rectangle)).collect();
+        let wanted: HashMap<&str, &Rectangle3D> = expected.entries.iter().map(|entry| (entry.path.as_str(), &entry.rectangle)).collect();
+        for (path, rectangle) in &wanted {
+            match actual.get(path) {
+                Some(computed) if !rectangle_eq(**computed, **rectangle, tolerance) => {
+                    mismatches.push(format!("'{}' expected {} got {}", path, format_rectangle(rectangle), format_rectangle(computed)));
+                },
+                Some(_) => {},
+                None => mismatches.push(format!("'{}' is missing", path)),
+            }
+        }
+        for path in actual.keys().filter(|path| !wanted.contains_key(*path)) {
+            mismatches.push(format!("'{}' is unexpected", path));
+        }
+        mismatches
+    }
+}
+impl fmt::Display for LayoutSnapshot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            writeln!(f, "{} | {}", entry.path, format_rectangle(&entry.rectangle))?;
+        }
+        Ok(())
+    }
+}
+
+
+// #=================#
+// #=== ASSERTION ===#
+
+/// Computes the tree inside root of the given size and asserts that it matches the expected snapshot text.
+/// ## ⚠️ Warning
+/// * Panics with all mismatches and the full actual snapshot, so it can be copied into the test if the change is intended.
+#[track_caller]
+pub fn assert_layout_snapshot<M: Default + UiComponent, N: Default + UiComponent>(tree: &mut UiTree<M, N>, size: impl Into<Vec2>, expected: &str, tolerance: f32) {
+    let expected = match LayoutSnapshot::parse(expected) {
+        Ok(expected) => expected,
+        Err(error) => panic!("{}", error),
+    };
+    let actual = LayoutSnapshot::compute(tree, size);
+    let mismatches = actual.compare(&expected, tolerance);
+    if !mismatches.is_empty() {
+        panic!("Layout snapshot mismatch:\n{}\n\nActual snapshot:\n{}", mismatches.join("\n"), actual);
+    }
+}
+
+/// Asserts that position and size of two rectangles differ by at most `tolerance`.
+#[track_caller]
+pub fn assert_rectangle_eq(actual: Rectangle3D, expected: Rectangle3D, tolerance: f32) {
+    if !rectangle_eq(actual, expected, tolerance) {
+        panic!("Rectangle mismatch: expected {} got {}", format_rectangle(&expected), format_rectangle(&actual));
+    }
+}
+
+
+// #===============#
+// #=== HELPERS ===#
+
+fn rectangle_eq(a: Rectangle3D, b: Rectangle3D, tolerance: f32) -> bool {
+    a.pos.abs_diff_eq(b.pos, tolerance) && a.size.abs_diff_eq(b.size, tolerance)
+}
+/// Rounds to 2 decimal places. Also turns `-0.0` into `0.0`, so the text output is stable.
+fn round(rectangle: Rectangle3D) -> Rectangle3D {
+    let round = |value: f32| (value * 100.0).round() / 100.0 + 0.0;
+    Rectangle3D {
+        pos: Vec3::new(round(rectangle.pos.x), round(rectangle.pos.y), round(rectangle.pos.z)),
+        size: Vec2::new(round(rectangle.size.x), round(rectangle.size.y)),
+        ..Default::default()
+    }
+}
+fn format_rectangle(rectangle: &Rectangle3D) -> String {
+    let rectangle = round(*rectangle);
+    format!("pos: {:.2} {:.2} {:.2} | size: {:.2} {:.2}", rectangle.pos.x, rectangle.pos.y, rectangle.pos.z, rectangle.size.x, rectangle.size.y)
+}
+fn parse_floats(text: &str) -> Option<Vec<f32>> {
+    text.split_whitespace().map(|value| value.parse().ok()).collect()
+}
+
+
+// #=============#
+// #=== TESTS ===#
+
+#[cfg(test)]
+mod test {
+    use super::{LayoutSnapshot, SnapshotError, assert_layout_snapshot, assert_rectangle_eq};
+    use crate::prelude::*;
+    use crate::import::*;
+    use crate::{NodeDataTrait, Window, Solid, Rectangle3D};
+
+    #[test]
+    fn snapshot () {
+        let mut tree: UiTree = UiTree::new("Root");
+        Window::new().pos(Abs(Vec2::new(10.0, 20.0))).size(Prc(Vec2::splat(50.0))).build(&mut tree, "Window").unwrap();
+        tree.borrow_node_mut("Window").unwrap().data.as_mut().unwrap().stack = FlexBox::new().gap(Abs(Vec2::splat(4.0)));
+        Div::new().margin(Abs(Vec4::splat(2.0))).build(&mut tree, "Window/Div1").unwrap();
+        Div::new().pad(Abs(Vec4::splat(1.0))).build(&mut tree, "Window/Div2").unwrap();
+        tree.borrow_node_mut("Window/Div1").unwrap().data.as_mut().unwrap().content_size = Vec2::new(30.0, 10.0);
+        tree.borrow_node_mut("Window/Div2").unwrap().data.as_mut().unwrap().content_size = Vec2::new(20.0, 5.0);
+        Solid::new().size(Abs(Vec2::new(4.0, 2.0))).build(&mut tree, "Window/Div2/Solid").unwrap();
+
+        assert_layout_snapshot(&mut tree, (800.0, 600.0), "
+            Window | pos: 10.00 20.00 1.00 | size: 400.00 300.00
+            Window/Div1 | pos: 2.00 2.00 2.00 | size: 30.00 10.00
+            Window/Div2 | pos: 38.00 0.00 2.00 | size: 22.00 7.00
+            Window/Div2/Solid | pos: 42.00 0.00 3.00 | size: 14.00 7.00
+        ", 0.01);
+
+        let snapshot = LayoutSnapshot::capture(&tree);
+        assert_eq!(LayoutSnapshot::parse(&snapshot.to_string()), Ok(snapshot.clone()));
+        assert_eq!(LayoutSnapshot::parse("Window | pos: 1 2 | size: 3 4"), Err(SnapshotError("Window | pos: 1 2 | size: 3 4".into())));
+        assert_rectangle_eq(tree.borrow_data("Window").unwrap().unwrap().rectangle, Rectangle3D { pos: Vec3::new(10.004, 20.0, 1.0), size: Vec2::new(400.0, 300.0), ..Default::default() }, 0.01);
+
+        let mut expected = snapshot.clone();
+        expected.entries[1].rectangle.size.x += 1.0;
+        expected.entries.pop();
+        let mismatches = snapshot.compare(&expected, 0.01);
+        assert_eq!(mismatches.len(), 2, "{:?}", mismatches);
+    }
+}